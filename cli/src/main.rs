@@ -1,11 +1,19 @@
+use std::path::PathBuf;
+
 use anyhow::Context;
 use clap::{CommandFactory, Parser};
 use maccel_core::{
-    fixedptc::Fpt,
-    persist::{ParamStore, SysFsStore},
+    fixedptc::{FixedptWidth, Fpt},
+    inputspeed, lint,
+    persist::{
+        write_modprobe_options, FileStore, ParamStore, Profiles, SysFsStore, Transaction,
+        DEFAULT_MODULE_NAME, MODPROBE_OPTIONS_PATH,
+    },
+    precision,
     subcommads::*,
-    AccelMode, Param, ALL_COMMON_PARAMS, ALL_LINEAR_PARAMS, ALL_CLASSIC_PARAMS, ALL_NATURAL_PARAMS,
-    ALL_SYNCHRONOUS_PARAMS,
+    units, AccelMode, LookupTable, Param, ALL_CLASSIC_PARAMS, ALL_COMMON_PARAMS, ALL_JUMP_PARAMS,
+    ALL_LINEAR_PARAMS, ALL_MACOS_PARAMS, ALL_NATURAL_PARAMS, ALL_PARAMS, ALL_POWER_PARAMS,
+    ALL_SYNCHRONOUS_PARAMS, ALL_WINDOWS_PARAMS,
 };
 use maccel_tui::run_tui;
 
@@ -13,6 +21,18 @@ use maccel_tui::run_tui;
 #[clap(author, about, version)]
 /// CLI to control the parameters for the maccel driver.
 struct Cli {
+    /// Work on the parameters saved in a TOML file instead of the live kernel module
+    #[arg(long, global = true)]
+    file: Option<PathBuf>,
+
+    /// Look for the kernel module's parameters, device and saved state under this directory instead of `/`
+    #[arg(long, global = true, env = "MACCEL_SYSFS_ROOT", default_value = "/")]
+    sysfs_root: PathBuf,
+
+    /// The name the kernel module is loaded as
+    #[arg(long, global = true, env = "MACCEL_MODULE_NAME", default_value = DEFAULT_MODULE_NAME)]
+    module_name: String,
+
     #[clap(subcommand)]
     command: Option<CLiCommands>,
 }
@@ -33,6 +53,64 @@ enum CLiCommands {
         #[clap(subcommand)]
         command: CliSubcommandGetParams,
     },
+    /// Apply the parameters and mode saved from the last time they were set,
+    /// e.g. when the kernel module is loaded on boot
+    Restore,
+    /// Set parameters back to the values the driver starts with
+    Reset {
+        #[clap(subcommand)]
+        command: ResetCommands,
+    },
+    /// Save, load and manage named snapshots of all the parameters and the mode
+    Profile {
+        /// The directory where profiles are kept [default: ~/.config/maccel/profiles]
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        #[clap(subcommand)]
+        command: ProfileCommands,
+    },
+    /// Report how far the driver's fixed-point sensitivity is from the exact curve,
+    /// for both widths of fixed-point numbers the driver can be built with
+    Precision {
+        /// Check the parameters and mode saved in this profile, instead of the current ones
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// The directory where profiles are kept [default: ~/.config/maccel/profiles]
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Check this mode's curve, instead of the current mode's
+        #[arg(long)]
+        mode: Option<AccelMode>,
+
+        /// The fastest input speed to check, in counts/ms
+        #[arg(long, default_value_t = 128.0)]
+        max_speed: f64,
+    },
+    /// Check the curve for what likely makes it feel wrong, like a sensitivity that jumps,
+    /// or parameters that don't change anything
+    Lint {
+        /// Check the parameters and mode saved in this profile, instead of the current ones
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// The directory where profiles are kept [default: ~/.config/maccel/profiles]
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Check this mode's curve, instead of the current mode's
+        #[arg(long)]
+        mode: Option<AccelMode>,
+    },
+    /// Manage the options file in /etc/modprobe.d/ that makes the driver load
+    /// with the current parameters, instead of the default curve until `restore`.
+    /// It's a snapshot, later changes aren't in it until it's written again
+    Modprobe {
+        #[clap(subcommand)]
+        command: ModprobeCommands,
+    },
     /// Generate a completions file for a specified shell
     Completion {
         // The shell for which to generate completions
@@ -46,6 +124,45 @@ enum CLiCommands {
     },
 }
 
+#[derive(clap::Subcommand)]
+enum ResetCommands {
+    /// Reset every parameter, and the mode
+    All,
+    /// Reset the common parameters
+    Common,
+    /// Reset the parameters for a curve, or clear the points of the look-up table
+    Mode { mode: AccelMode },
+}
+
+#[derive(clap::Subcommand)]
+enum ProfileCommands {
+    /// Save the current parameters and mode as a profile
+    Save { name: String },
+    /// Apply the parameters and mode saved in a profile
+    Load { name: String },
+    /// List the saved profiles
+    List,
+    /// Delete a saved profile
+    Delete { name: String },
+    /// Print the mode and parameters saved in a profile
+    Show { name: String },
+}
+
+#[derive(clap::Subcommand)]
+enum ModprobeCommands {
+    /// Write the current parameters and mode as the driver's load options,
+    /// run it again after changing them to keep the file up to date
+    Write {
+        #[arg(long, default_value = MODPROBE_OPTIONS_PATH)]
+        path: PathBuf,
+    },
+    /// Remove the options file, so the driver loads with its defaults
+    Remove {
+        #[arg(long, default_value = MODPROBE_OPTIONS_PATH)]
+        path: PathBuf,
+    },
+}
+
 #[cfg(debug_assertions)]
 #[derive(Debug, clap::Subcommand)]
 enum DebugCommands {
@@ -53,42 +170,71 @@ enum DebugCommands {
 }
 
 fn main() -> anyhow::Result<()> {
-    let args = Cli::parse();
+    let mut args = Cli::parse();
 
     // tracing_subscriber::fmt()
     //     .with_max_level(Level::DEBUG)
     //     .with_writer(File::create("./maccel.log")?)
     //     .init();
 
-    let mut param_store = SysFsStore;
+    let command = args.command.take().unwrap_or_default();
 
-    match args.command.unwrap_or_default() {
-        CLiCommands::Set { command } => match command {
-            CliSubcommandSetParams::Param { name, value } => param_store.set(name, value)?,
-            CliSubcommandSetParams::All { command } => match command {
-                SetParamByModesSubcommands::Linear(param_args) => {
-                    param_store.set_all_linear(param_args)?
-                }
-                SetParamByModesSubcommands::Classic(param_args) => {
-                    param_store.set_all_classic(param_args)?
-                }
-                SetParamByModesSubcommands::Natural(param_args) => {
-                    param_store.set_all_natural(param_args)?
-                }
-                SetParamByModesSubcommands::Common(param_args) => {
-                    param_store.set_all_common(param_args)?
-                }
-                SetParamByModesSubcommands::Synchronous(param_args) => {
-                    param_store.set_all_synchronous(param_args)?
+    match &args.file {
+        Some(path) => run(&args, command, FileStore::open(path)?),
+        None => run(&args, command, sysfs_store(&args)),
+    }
+}
+
+fn sysfs_store(args: &Cli) -> SysFsStore {
+    SysFsStore::new(&args.sysfs_root, &args.module_name)
+}
+
+fn run<PS: ParamStore + 'static>(
+    args: &Cli,
+    command: CLiCommands,
+    mut param_store: PS,
+) -> anyhow::Result<()> {
+    match command {
+        CLiCommands::Set { command } => {
+            let transaction = match command {
+                CliSubcommandSetParams::Param { name, value } => {
+                    Transaction::new().set(name, units::parse_param_value(name, &value)?)
                 }
-            },
-            CliSubcommandSetParams::Mode { mode } => SysFsStore::set_current_accel_mode(mode),
-        },
+                CliSubcommandSetParams::All { command } => match command {
+                    SetParamByModesSubcommands::Linear(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Classic(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Natural(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Common(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Synchronous(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Jump(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Power(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Windows(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::Macos(param_args) => param_args.into(),
+                    SetParamByModesSubcommands::LookupTable(param_args) => param_args.into(),
+                },
+                CliSubcommandSetParams::Mode { mode } => Transaction::new().mode(mode),
+            };
+
+            apply_with_warnings(&mut param_store, transaction)?
+        }
+        CLiCommands::Reset { command } => {
+            let transaction = match command {
+                ResetCommands::All => Transaction::defaults(ALL_PARAMS)
+                    .lookup_table(LookupTable::default())
+                    .mode(AccelMode::default()),
+                ResetCommands::Common => Transaction::defaults(ALL_COMMON_PARAMS),
+                ResetCommands::Mode {
+                    mode: AccelMode::LookupTable,
+                } => Transaction::new().lookup_table(LookupTable::default()),
+                ResetCommands::Mode { mode } => Transaction::defaults(mode.params()),
+            };
+
+            apply_with_warnings(&mut param_store, transaction)?
+        }
         CLiCommands::Get { command } => match command {
             CliSubcommandGetParams::Param { name } => {
                 let value = param_store.get(&name)?;
-                let string_value: &str = (&value).try_into()?;
-                println!("{}", string_value);
+                println!("{}", value.to_driver_string());
             }
             CliSubcommandGetParams::All {
                 oneline,
@@ -96,41 +242,84 @@ fn main() -> anyhow::Result<()> {
                 command,
             } => match command {
                 GetParamsByModesSubcommands::Linear => {
-                    print_all_params(ALL_LINEAR_PARAMS.iter(), oneline, quiet)?;
+                    print_all_params(&param_store, ALL_LINEAR_PARAMS.iter(), oneline, quiet)?;
                 }
                 GetParamsByModesSubcommands::Classic => {
-                    print_all_params(ALL_CLASSIC_PARAMS.iter(), oneline, quiet)?;
+                    print_all_params(&param_store, ALL_CLASSIC_PARAMS.iter(), oneline, quiet)?;
                 }
                 GetParamsByModesSubcommands::Natural => {
-                    print_all_params(ALL_NATURAL_PARAMS.iter(), oneline, quiet)?;
+                    print_all_params(&param_store, ALL_NATURAL_PARAMS.iter(), oneline, quiet)?;
                 }
                 GetParamsByModesSubcommands::Common => {
-                    print_all_params(ALL_COMMON_PARAMS.iter(), oneline, quiet)?;
+                    print_all_params(&param_store, ALL_COMMON_PARAMS.iter(), oneline, quiet)?;
                 }
                 GetParamsByModesSubcommands::Synchronous => {
-                    print_all_params(ALL_SYNCHRONOUS_PARAMS.iter(), oneline, quiet)?;
+                    print_all_params(&param_store, ALL_SYNCHRONOUS_PARAMS.iter(), oneline, quiet)?;
+                }
+                GetParamsByModesSubcommands::Jump => {
+                    print_all_params(&param_store, ALL_JUMP_PARAMS.iter(), oneline, quiet)?;
+                }
+                GetParamsByModesSubcommands::Power => {
+                    print_all_params(&param_store, ALL_POWER_PARAMS.iter(), oneline, quiet)?;
+                }
+                GetParamsByModesSubcommands::Windows => {
+                    print_all_params(&param_store, ALL_WINDOWS_PARAMS.iter(), oneline, quiet)?;
+                }
+                GetParamsByModesSubcommands::Macos => {
+                    print_all_params(&param_store, ALL_MACOS_PARAMS.iter(), oneline, quiet)?;
+                }
+                GetParamsByModesSubcommands::LookupTable => {
+                    print_lookup_table(&param_store, oneline, quiet)?;
                 }
             },
-            CliSubcommandGetParams::Mode => {
-                let mode = SysFsStore::get_current_accel_mode();
-                println!("{}\n", mode.as_title());
-                match mode {
-                    AccelMode::Linear => {
-                        print_all_params(ALL_LINEAR_PARAMS.iter(), false, false)?;
-                    }
-                    AccelMode::Classic => {
-                        print_all_params(ALL_CLASSIC_PARAMS.iter(), false, false)?;
-                    }
-                    AccelMode::Natural => {
-                        print_all_params(ALL_NATURAL_PARAMS.iter(), false, false)?;
-                    }
-                    AccelMode::Synchronous => {
-                        print_all_params(ALL_SYNCHRONOUS_PARAMS.iter(), false, false)?;
+            CliSubcommandGetParams::Mode => print_mode_params(&param_store)?,
+        },
+        CLiCommands::Restore => {
+            for err in sysfs_store(args).restore()? {
+                eprintln!("warning: not restored, {err}");
+            }
+        }
+        CLiCommands::Profile { dir, command } => {
+            let profiles = profiles(dir)?;
+
+            match command {
+                ProfileCommands::Save { name } => profiles.save(&name, &param_store)?,
+                ProfileCommands::Load { name } => profiles.load(&name, &mut param_store)?,
+                ProfileCommands::List => {
+                    for name in profiles.list()? {
+                        println!("{}", name);
                     }
                 }
+                ProfileCommands::Delete { name } => profiles.delete(&name)?,
+                ProfileCommands::Show { name } => {
+                    let profile = profiles.open(&name)?;
+                    print_all_params(&profile, ALL_COMMON_PARAMS.iter(), false, false)?;
+                    println!();
+                    print_mode_params(&profile)?;
+                }
             }
+        }
+        CLiCommands::Precision {
+            profile,
+            dir,
+            mode,
+            max_speed,
+        } => match profile {
+            Some(name) => print_precision(&profiles(dir)?.open(&name)?, mode, max_speed)?,
+            None => print_precision(&param_store, mode, max_speed)?,
         },
-        CLiCommands::Tui => run_tui()?,
+        CLiCommands::Lint { profile, dir, mode } => match profile {
+            Some(name) => print_lints(&profiles(dir)?.open(&name)?, mode)?,
+            None => print_lints(&param_store, mode)?,
+        },
+        CLiCommands::Modprobe { command } => match command {
+            ModprobeCommands::Write { path } => {
+                write_modprobe_options(&path, &args.module_name, &param_store)?
+            }
+            ModprobeCommands::Remove { path } => std::fs::remove_file(&path)
+                .context(format!("failed to remove {}", path.display()))?,
+        },
+        CLiCommands::Tui => run_tui(param_store, inputspeed::device_path(&args.sysfs_root))?,
         CLiCommands::Completion { shell } => {
             clap_complete::generate(shell, &mut Cli::command(), "maccel", &mut std::io::stdout())
         }
@@ -157,7 +346,123 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn profiles(dir: Option<PathBuf>) -> maccel_core::Result<Profiles> {
+    Ok(Profiles::new(match dir {
+        Some(dir) => dir,
+        None => Profiles::default_dir()?,
+    }))
+}
+
+fn print_precision(
+    param_store: &impl ParamStore,
+    mode: Option<AccelMode>,
+    max_speed: f64,
+) -> anyhow::Result<()> {
+    let mode = match mode {
+        Some(mode) => mode,
+        None => param_store.get_current_accel_mode()?,
+    };
+    let params = param_store.get_all()?;
+
+    println!(
+        "{}, compared at {} input speeds up to {max_speed} counts/ms\n",
+        mode.as_title(),
+        precision::SAMPLES
+    );
+    println!(
+        "{:>6}  {:>12}  {:>10}  {:>12}",
+        "bits", "max error", "at speed", "mean error"
+    );
+    for width in FixedptWidth::ALL {
+        let report = precision::precision_report(mode, &params, width, max_speed);
+        let this_build = if width == FixedptWidth::CURRENT {
+            "  (this build)"
+        } else {
+            ""
+        };
+        println!(
+            "{:>6}  {:>12.3e}  {:>10.3}  {:>12.3e}{this_build}",
+            width.bits(),
+            report.max_error,
+            report.max_error_at,
+            report.mean_error
+        );
+    }
+
+    Ok(())
+}
+
+fn print_lints(param_store: &impl ParamStore, mode: Option<AccelMode>) -> anyhow::Result<()> {
+    let mode = match mode {
+        Some(mode) => mode,
+        None => param_store.get_current_accel_mode()?,
+    };
+    let params = param_store.get_all()?;
+
+    let lints = lint::lint(mode, &params);
+    if lints.is_empty() {
+        println!(
+            "{}: no issues found up to {} counts/ms",
+            mode.as_title(),
+            lint::MAX_SPEED
+        );
+    }
+    for issue in lints {
+        println!("warning: {issue}");
+    }
+
+    Ok(())
+}
+
+/// Show the warnings about the parameters as they would be, then apply them.
+fn apply_with_warnings(
+    param_store: &mut impl ParamStore,
+    transaction: Transaction,
+) -> anyhow::Result<()> {
+    let validation = param_store.apply(&transaction)?;
+    for warning in validation.warnings() {
+        eprintln!("warning: {warning}");
+    }
+
+    Ok(())
+}
+
+fn print_mode_params(param_store: &impl ParamStore) -> anyhow::Result<()> {
+    let mode = param_store.get_current_accel_mode()?;
+    println!("{}\n", mode.as_title());
+    match mode {
+        AccelMode::Linear => {
+            print_all_params(param_store, ALL_LINEAR_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Classic => {
+            print_all_params(param_store, ALL_CLASSIC_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Natural => {
+            print_all_params(param_store, ALL_NATURAL_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Synchronous => {
+            print_all_params(param_store, ALL_SYNCHRONOUS_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Jump => {
+            print_all_params(param_store, ALL_JUMP_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Power => {
+            print_all_params(param_store, ALL_POWER_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Windows => {
+            print_all_params(param_store, ALL_WINDOWS_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::Macos => {
+            print_all_params(param_store, ALL_MACOS_PARAMS.iter(), false, false)?;
+        }
+        AccelMode::LookupTable => print_lookup_table(param_store, false, false)?,
+    }
+
+    Ok(())
+}
+
 fn print_all_params<'p>(
+    param_store: &impl ParamStore,
     params: impl Iterator<Item = &'p Param>,
     oneline: bool,
     quiet: bool,
@@ -166,12 +471,11 @@ fn print_all_params<'p>(
 
     let params = params
         .map(|p| {
-            SysFsStore.get(p).and_then(|_p: Fpt| {
-                let value: &str = (&_p).try_into()?;
-                Ok((p.display_name(), value.to_string()))
-            })
+            param_store
+                .get(p)
+                .map(|value| (p.display_name(), value.to_driver_string()))
         })
-        .collect::<anyhow::Result<Vec<_>>>()
+        .collect::<maccel_core::Result<Vec<_>>>()
         .context("failed to get all parameters")?;
 
     for (name, value) in params {
@@ -185,3 +489,28 @@ fn print_all_params<'p>(
 
     Ok(())
 }
+
+/// Print the points of the look-up table, one per line as in the files they're loaded from,
+/// unless `oneline`.
+fn print_lookup_table(
+    param_store: &impl ParamStore,
+    oneline: bool,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let delimiter = if oneline { " " } else { "\n" };
+
+    let table = param_store
+        .get_lookup_table()
+        .context("failed to get the look-up table")?;
+
+    for (idx, (speed, sens)) in table.points().iter().enumerate() {
+        if !quiet {
+            print!("Point {}:  \t", idx + 1);
+        }
+        print!("{}, {}", speed, sens);
+
+        print!("{}", delimiter);
+    }
+
+    Ok(())
+}
@@ -1,25 +1,48 @@
-use std::{
-    env::{self, consts::ARCH},
-    path::PathBuf,
-};
+use std::{env, fs, path::PathBuf};
 
 fn main() {
     let out = PathBuf::from(
         env::var("OUT_DIR").expect("Expected OUT_DIR to be defined in the environment"),
     );
 
-    let fixedpt_bits = match ARCH {
+    // The architecture we're building for, which isn't the one the build script runs on
+    // when cross-compiling.
+    let arch = env::var("CARGO_CFG_TARGET_ARCH")
+        .expect("Expected CARGO_CFG_TARGET_ARCH to be defined in the environment");
+
+    let fixedpt_bits = match arch.as_str() {
         "x86" => "32",
         #[cfg(feature = "long_bit_32")]
-        "x86_64" => "32",
+        "x86_64" | "aarch64" | "riscv64" => "32",
         #[cfg(not(feature = "long_bit_32"))]
-        "x86_64" => "64",
+        "x86_64" | "aarch64" | "riscv64" => "64",
+        // The Rust port doesn't depend on the architecture's C types.
+        _ if cfg!(feature = "pure_rust") && cfg!(feature = "long_bit_32") => "32",
+        _ if cfg!(feature = "pure_rust") => "64",
         a => panic!("unsupported/untested architecture: {a}"),
     };
+
+    println!("cargo::rustc-check-cfg=cfg(fixedpt_bits, values(\"32\", \"64\"))");
+    println!("cargo:rustc-cfg=fixedpt_bits=\"{fixedpt_bits}\"");
+
+    // How many points the look-up table takes, the driver's default unless it's set for both,
+    // e.g. `LUT_MAX_POINTS=128` for the driver built with `EXTRA_CFLAGS="-DLUT_MAX_POINTS=128"`.
+    println!("cargo:rerun-if-env-changed=LUT_MAX_POINTS");
+    let lut_max_points = env::var("LUT_MAX_POINTS").unwrap_or_else(|_| default_lut_max_points());
+    if !lut_max_points.parse::<usize>().is_ok_and(|n| n > 0) {
+        panic!("LUT_MAX_POINTS should be a positive number, not {lut_max_points:?}");
+    }
+    println!("cargo:rustc-env=LUT_MAX_POINTS={lut_max_points}");
+
+    if cfg!(feature = "pure_rust") {
+        return;
+    }
+
     let mut compiler = cc::Build::new();
     compiler
         .file("src/libmaccel.c")
-        .define("FIXEDPT_BITS", fixedpt_bits);
+        .define("FIXEDPT_BITS", fixedpt_bits)
+        .define("LUT_MAX_POINTS", lut_max_points.as_str());
 
     if cfg!(feature = "dbg") {
         compiler.define("DEBUG", "1");
@@ -33,7 +56,26 @@ fn main() {
     const DRIVER_DIR: &str = "../../driver";
     println!("cargo:rerun-if-changed=src/libmaccel.c");
     println!("cargo:rerun-if-changed={DRIVER_DIR}/accel/natural.h");
+    println!("cargo:rerun-if-changed={DRIVER_DIR}/accel/jump.h");
+    println!("cargo:rerun-if-changed={DRIVER_DIR}/accel/power.h");
+    println!("cargo:rerun-if-changed={DRIVER_DIR}/accel/windows.h");
+    println!("cargo:rerun-if-changed={DRIVER_DIR}/accel/lut.h");
+    println!("cargo:rerun-if-changed={DRIVER_DIR}/accel/macos.h");
     println!("cargo:rerun-if-changed={DRIVER_DIR}/accel.h");
     println!("cargo:rerun-if-changed={DRIVER_DIR}/accel_rs.h");
     println!("cargo:rerun-if-changed={DRIVER_DIR}/fixedptc.h");
+    println!("cargo:rerun-if-changed={DRIVER_DIR}/utils.h");
+}
+
+/// The `LUT_MAX_POINTS` that `driver/accel/lut.h` defines when it isn't given one.
+fn default_lut_max_points() -> String {
+    const LUT_H: &str = "../../driver/accel/lut.h";
+    println!("cargo:rerun-if-changed={LUT_H}");
+
+    let lut_h = fs::read_to_string(LUT_H).expect("Expected to read the driver's lut.h");
+    lut_h
+        .lines()
+        .find_map(|line| line.strip_prefix("#define LUT_MAX_POINTS "))
+        .map(|n| n.trim().to_string())
+        .expect("Expected lut.h to define LUT_MAX_POINTS")
 }
@@ -2,15 +2,15 @@ use std::{
     cell::{Ref, RefCell, RefMut},
     ops::Deref,
     rc::Rc,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
-use anyhow::Context;
-
 use crate::{
     libmaccel::fixedptc::Fpt,
     params::{AllParamArgs, Param},
-    persist::ParamStore,
-    AccelMode,
+    persist::{ParamStore, Transaction},
+    validation::Validation,
+    AccelMode, Curve, LookupTable, LookupTableParamArgs,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -29,49 +29,60 @@ impl Parameter {
 pub struct TuiContext<PS: ParamStore> {
     pub current_mode: AccelMode,
     parameters: Vec<Parameter>,
+    lookup_table: LookupTable,
     parameter_store: PS,
 }
 
 impl<PS: ParamStore> TuiContext<PS> {
-    pub fn new(parameter_store: PS, parameters: &[Param]) -> Self {
-        Self {
-            current_mode: PS::get_current_accel_mode(),
+    pub fn new(parameter_store: PS, parameters: &[Param]) -> crate::Result<Self> {
+        Ok(Self {
+            current_mode: parameter_store.get_current_accel_mode()?,
             parameters: parameters
                 .iter()
-                .map(|&p| {
-                    let value = parameter_store
-                        .get(&p)
-                        .expect("Failed to get a param from store while initializing TuiContext");
-                    Parameter::new(p, value)
-                })
-                .collect(),
+                .map(|&p| Ok(Parameter::new(p, parameter_store.get(&p)?)))
+                .collect::<crate::Result<_>>()?,
+            lookup_table: parameter_store.get_lookup_table()?,
             parameter_store,
-        }
+        })
     }
 
     pub fn parameter(&self, param: Param) -> Option<&Parameter> {
         self.parameters.iter().find(|p| p.tag == param)
     }
 
-    pub fn update_param_value(&mut self, param_id: Param, value: f64) -> anyhow::Result<()> {
-        let param = self
-            .parameters
-            .iter_mut()
-            .find(|p| p.tag == param_id)
-            .context("Unknown parameter, cannot update")?;
+    pub fn lookup_table(&self) -> &LookupTable {
+        &self.lookup_table
+    }
 
-        self.parameter_store.set(param.tag, value)?;
-        param.value = value.into();
+    /// Set the parameter's value, unless it would make a broken curve with the others,
+    /// and give back the warnings about the parameters with the new value.
+    pub fn update_param_value(&mut self, param_id: Param, value: f64) -> crate::Result<Validation> {
+        let transaction = Transaction::new().set(param_id, value);
+        let validation = self.parameter_store.apply(&transaction)?;
+        if let Some(param) = self.parameters.iter_mut().find(|p| p.tag == param_id) {
+            param.value = value.into();
+        }
+        Ok(validation)
+    }
+
+    /// Switch modes, keeping the current one if the store fails to.
+    pub fn update_current_mode(&mut self, mode: AccelMode) -> crate::Result<()> {
+        self.parameter_store.set_current_accel_mode(mode)?;
+        self.current_mode = mode;
         Ok(())
     }
 
-    pub fn reset_current_parameters(&mut self) {
+    pub fn reset_current_parameters(&mut self) -> crate::Result<()> {
         for p in self.parameters.iter_mut() {
-            p.value = self
-                .parameter_store
-                .get(&p.tag)
-                .expect("failed to read and initialize a parameter's value");
+            p.value = self.parameter_store.get(&p.tag)?;
         }
+        self.lookup_table = self.parameter_store.get_lookup_table()?;
+        Ok(())
+    }
+
+    /// The current mode's curve, with the values being edited.
+    pub fn curve(&self) -> Curve {
+        Curve::from_args(self.current_mode, &self.params_snapshot())
     }
 
     pub fn params_snapshot(&self) -> AllParamArgs {
@@ -104,6 +115,20 @@ impl<PS: ParamStore> TuiContext<PS> {
             smooth: get!(Smooth),
             motivity: get!(Motivity),
             sync_speed: get!(SyncSpeed),
+            jump_speed: get!(JumpSpeed),
+            jump_sens: get!(JumpSens),
+            smooth_jump: get!(SmoothJump),
+            scale_power: get!(ScalePower),
+            exponent_power: get!(ExponentPower),
+            output_offset_power: get!(OutputOffsetPower),
+            output_cap_power: get!(OutputCapPower),
+            slider_windows: get!(SliderWindows),
+            refresh_rate_windows: get!(RefreshRateWindows),
+            screen_dpi_windows: get!(ScreenDpiWindows),
+            tracking_speed: get!(TrackingSpeed),
+            lookup_table: LookupTableParamArgs {
+                points: self.lookup_table.clone(),
+            },
         }
     }
 }
@@ -145,7 +170,69 @@ impl<PS: ParamStore> ContextRef<PS> {
         self.inner.borrow()
     }
 
-    pub fn get_mut(&mut self) -> RefMut<TuiContext<PS>> {
+    pub fn get_mut(&mut self) -> RefMut<'_, TuiContext<PS>> {
         self.inner.deref().borrow_mut()
     }
 }
+
+/// Like [`ContextRef`], but it can be shared between threads, e.g. in a service,
+/// as long as the store can.
+///
+/// A thread panicking while holding the lock doesn't make the context unusable:
+/// every update to it is a single assignment, after writing to the store.
+#[derive(Debug)]
+pub struct SyncContextRef<PS: ParamStore> {
+    inner: Arc<RwLock<TuiContext<PS>>>,
+}
+
+impl<PS: ParamStore> Clone for SyncContextRef<PS> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<PS: ParamStore> SyncContextRef<PS> {
+    pub fn new(value: TuiContext<PS>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(value)),
+        }
+    }
+
+    pub fn get(&self) -> RwLockReadGuard<'_, TuiContext<PS>> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get_mut(&self) -> RwLockWriteGuard<'_, TuiContext<PS>> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn sync_context_is_shared_between_threads() {
+    use crate::{persist::FileStore, ALL_PARAMS};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sync-context.toml");
+    let mut store = FileStore::open(&path).unwrap();
+    store.apply(&Transaction::defaults(ALL_PARAMS)).unwrap();
+
+    let context = SyncContextRef::new(TuiContext::new(store, ALL_PARAMS).unwrap());
+    let threads: Vec<_> = [1.5, 2.0]
+        .into_iter()
+        .zip([Param::SensMult, Param::YxRatio])
+        .map(|(value, param)| {
+            let context = context.clone();
+            std::thread::spawn(move || context.get_mut().update_param_value(param, value))
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap().unwrap();
+    }
+
+    let curve = context.get().curve();
+    assert_eq!(curve.common.sens_mult, 1.5);
+    assert_eq!(curve.common.yx_ratio, 2.0);
+}
@@ -0,0 +1,288 @@
+//! A single curve, with the values of only the parameters it uses,
+//! for when [`AllParamArgs`], with every mode's parameters, is more than needed.
+
+use crate::{
+    ALL_COMMON_PARAMS, AccelMode, AccelParams, AccelParamsByMode, AllParamArgs, CommonParamArgs,
+    CurveConfig, Error, Param, SensXY,
+    fixedptc::Fpt,
+    params::validate::validate_param_value,
+    sens_fns::accel_params_sensitivity,
+    validation::{Validation, validate_params},
+};
+
+/// One mode's curve, and the common parameters.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub common: CommonParamArgs,
+    pub config: CurveConfig,
+}
+
+impl Curve {
+    /// Check every value, then the parameters together, see [`validate_params`].
+    /// Fails on any error, the warnings are in [`Curve::validate`].
+    pub fn new(common: CommonParamArgs, config: CurveConfig) -> crate::Result<Self> {
+        let curve = Self { common, config };
+
+        for (param, value) in curve.values() {
+            validate_param_value(param, value)?;
+        }
+        curve.validate().into_result()?;
+
+        Ok(curve)
+    }
+
+    /// Build the curve for `mode`, starting from the values the driver starts with.
+    pub fn builder(mode: AccelMode) -> CurveBuilder {
+        CurveBuilder {
+            mode,
+            values: vec![],
+        }
+    }
+
+    /// The curve for `mode`, with the values of the parameters it uses in `args`,
+    /// which are as close as fixed-point numbers get to the values they were set to.
+    pub fn from_args(mode: AccelMode, args: &AllParamArgs) -> Self {
+        Self {
+            common: args.into(),
+            config: CurveConfig::from_args(mode, args),
+        }
+    }
+
+    pub fn mode(&self) -> AccelMode {
+        self.config.mode()
+    }
+
+    /// The value of every parameter the curve uses, the common ones first.
+    pub fn values(&self) -> Vec<(Param, f64)> {
+        let CommonParamArgs {
+            sens_mult,
+            yx_ratio,
+            input_dpi,
+        } = self.common;
+
+        let mut values = vec![
+            (Param::SensMult, sens_mult),
+            (Param::YxRatio, yx_ratio),
+            (Param::InputDpi, input_dpi),
+        ];
+        values.extend(self.config.values());
+        values
+    }
+
+    /// Every parameter's value, those of the other modes at the values the driver starts with.
+    pub fn to_all_param_args(&self) -> AllParamArgs {
+        let values = self.values();
+        let mut args = AllParamArgs::from_fn(|param| {
+            let value = values
+                .iter()
+                .find(|(p, _)| *p == param)
+                .map_or(param.info().default, |&(_, v)| v);
+            Fpt::from(value)
+        });
+        if let CurveConfig::LookupTable(table) = &self.config {
+            args.lookup_table = table.clone();
+        }
+        args
+    }
+
+    /// The argument of the sensitivity function, as the driver takes it.
+    pub fn to_accel_params(&self) -> AccelParams {
+        AccelParams {
+            sens_mult: self.common.sens_mult.into(),
+            yx_ratio: self.common.yx_ratio.into(),
+            input_dpi: self.common.input_dpi.into(),
+            by_mode: self.config.to_params_by_mode(),
+        }
+    }
+
+    /// Check the parameters together, see [`validate_params`].
+    pub fn validate(&self) -> Validation {
+        validate_params(self.mode(), &self.to_all_param_args())
+    }
+
+    /// The ratio of output speed to input speed, see [`sensitivity`](crate::sensitivity).
+    pub fn evaluate(&self, speed: f64) -> SensXY {
+        accel_params_sensitivity(speed, &self.to_accel_params())
+    }
+}
+
+impl From<&AccelParams> for Curve {
+    fn from(params: &AccelParams) -> Self {
+        let common = CommonParamArgs {
+            sens_mult: params.sens_mult.into(),
+            yx_ratio: params.yx_ratio.into(),
+            input_dpi: params.input_dpi.into(),
+        };
+        let config = match &params.by_mode {
+            AccelParamsByMode::Linear(p) => CurveConfig::Linear(crate::LinearParamArgs {
+                accel_linear: p.accel_linear.into(),
+                offset_linear: p.offset_linear.into(),
+                output_cap_linear: p.output_cap_linear.into(),
+            }),
+            AccelParamsByMode::Classic(p) => CurveConfig::Classic(crate::ClassicParamArgs {
+                accel_classic: p.accel_classic.into(),
+                power_classic: p.power_classic.into(),
+                offset_classic: p.offset_classic.into(),
+                output_cap_classic: p.output_cap_classic.into(),
+            }),
+            AccelParamsByMode::Natural(p) => CurveConfig::Natural(crate::NaturalParamArgs {
+                decay_rate: p.decay_rate.into(),
+                offset_natural: p.offset_natural.into(),
+                limit: p.limit.into(),
+            }),
+            AccelParamsByMode::Synchronous(p) => {
+                CurveConfig::Synchronous(crate::SynchronousParamArgs {
+                    gamma: p.gamma.into(),
+                    smooth: p.smooth.into(),
+                    motivity: p.motivity.into(),
+                    sync_speed: p.sync_speed.into(),
+                })
+            }
+            AccelParamsByMode::Jump(p) => CurveConfig::Jump(crate::JumpParamArgs {
+                jump_speed: p.jump_speed.into(),
+                jump_sens: p.jump_sens.into(),
+                smooth_jump: p.smooth_jump.into(),
+            }),
+            AccelParamsByMode::Power(p) => CurveConfig::Power(crate::PowerParamArgs {
+                scale_power: p.scale_power.into(),
+                exponent_power: p.exponent_power.into(),
+                output_offset_power: p.output_offset_power.into(),
+                output_cap_power: p.output_cap_power.into(),
+            }),
+            AccelParamsByMode::Windows(p) => CurveConfig::Windows(crate::WindowsParamArgs {
+                slider_windows: p.slider_windows.into(),
+                refresh_rate_windows: p.refresh_rate_windows.into(),
+                screen_dpi_windows: p.screen_dpi_windows.into(),
+            }),
+            AccelParamsByMode::Macos(p) => CurveConfig::Macos(crate::MacosParamArgs {
+                tracking_speed: p.tracking_speed.into(),
+            }),
+            AccelParamsByMode::LookupTable(p) => {
+                CurveConfig::LookupTable(crate::LookupTableParamArgs {
+                    points: p.as_ref().into(),
+                })
+            }
+        };
+
+        Self { common, config }
+    }
+}
+
+/// Builds a [`Curve`] one parameter at a time, see [`Curve::builder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveBuilder {
+    mode: AccelMode,
+    values: Vec<(Param, f64)>,
+}
+
+impl CurveBuilder {
+    /// Set `param` to `value`, replacing any value already set for it.
+    pub fn set(mut self, param: Param, value: f64) -> Self {
+        match self.values.iter_mut().find(|(p, _)| *p == param) {
+            Some((_, v)) => *v = value,
+            None => self.values.push((param, value)),
+        }
+        self
+    }
+
+    /// Check and build the curve, see [`Curve::new`].
+    /// Fails on a parameter that's not for this curve.
+    pub fn build(self) -> crate::Result<Curve> {
+        for &(param, _) in &self.values {
+            if !ALL_COMMON_PARAMS.contains(&param) && !self.mode.params().contains(&param) {
+                return Err(Error::invalid_value(
+                    param,
+                    format!("not a parameter of the {} curve", self.mode.key()),
+                ));
+            }
+        }
+
+        let value_of = |param: Param| {
+            self.values
+                .iter()
+                .find(|(p, _)| *p == param)
+                .map_or(param.info().default, |&(_, v)| v)
+        };
+        let common = CommonParamArgs {
+            sens_mult: value_of(Param::SensMult),
+            yx_ratio: value_of(Param::YxRatio),
+            input_dpi: value_of(Param::InputDpi),
+        };
+
+        Curve::new(common, CurveConfig::from_fn(self.mode, value_of))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn curves_round_trip_and_evaluate_like_the_driver() {
+    use crate::LinearParamArgs;
+
+    let curve = Curve::builder(AccelMode::Linear)
+        .set(Param::AccelLinear, 0.25)
+        .set(Param::OffsetLinear, 2.0)
+        .set(Param::SensMult, 1.5)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        curve.config,
+        CurveConfig::Linear(LinearParamArgs {
+            accel_linear: 0.25,
+            offset_linear: 2.0,
+            output_cap_linear: 0.0,
+        })
+    );
+    assert_eq!(curve.common.input_dpi, 1000.0);
+
+    // Through fixed-point numbers, which these values are exact in.
+    let args = curve.to_all_param_args();
+    assert_eq!(args.limit, Fpt::from(1.5));
+    assert_eq!(Curve::from_args(AccelMode::Linear, &args), curve);
+    assert_eq!(Curve::from(&curve.to_accel_params()), curve);
+    assert_eq!(
+        Curve::new(curve.common, curve.config.clone()).unwrap(),
+        curve
+    );
+    assert_eq!(
+        curve.evaluate(10.0),
+        crate::sensitivity(10.0, AccelMode::Linear, &args)
+    );
+
+    assert!(matches!(
+        Curve::builder(AccelMode::Linear)
+            .set(Param::Limit, 2.0)
+            .build(),
+        Err(Error::InvalidValue {
+            param: Param::Limit,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Curve::builder(AccelMode::Natural)
+            .set(Param::Limit, 0.5)
+            .build(),
+        Err(Error::InvalidValue {
+            param: Param::Limit,
+            ..
+        })
+    ));
+    // A power below 1 with an offset makes the sensitivity jump right past the offset.
+    assert!(matches!(
+        Curve::builder(AccelMode::Classic)
+            .set(Param::PowerClassic, 0.5)
+            .set(Param::OffsetClassic, 2.0)
+            .build(),
+        Err(Error::InvalidCurve(_))
+    ));
+
+    let points = crate::LookupTable::new(&[(0.0, 1.0), (8.0, 2.0)]).unwrap();
+    let table = Curve::new(
+        curve.common,
+        CurveConfig::LookupTable(crate::LookupTableParamArgs { points }),
+    )
+    .unwrap();
+    assert_eq!(Curve::from(&table.to_accel_params()), table);
+    assert_eq!(table.evaluate(4.0).0, 1.5 * 1.5);
+}
@@ -0,0 +1,180 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Param, validation::Issue};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// There's no kernel module loaded under the name we're looking for.
+    ModuleNotLoaded {
+        module_path: PathBuf,
+    },
+    /// The loaded kernel module doesn't have the parameter, it's likely an older version.
+    UnsupportedParameter {
+        name: &'static str,
+    },
+    /// Not allowed to read or write, e.g. when not in the `maccel` group.
+    PermissionDenied {
+        path: PathBuf,
+    },
+    InvalidValue {
+        param: Param,
+        reason: String,
+    },
+    /// The parameters make a broken curve together.
+    InvalidCurve(Vec<Issue>),
+    UnknownMode(String),
+    /// The contents of a file we read from couldn't be interpreted.
+    Parse {
+        path: PathBuf,
+        reason: String,
+    },
+    InvalidNumber(String),
+    /// The points can't make the look-up table's curve.
+    InvalidLookupTable(String),
+    UnknownProfile(String),
+    InvalidProfileName(String),
+    ConfigDirNotFound,
+    /// Applying a transaction failed, and so did rolling back some of its parameters,
+    /// which are left as they were when it failed, by their name in the kernel module.
+    RollbackFailed {
+        source: Box<Error>,
+        failures: Vec<(&'static str, Error)>,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Error {
+    /// Wrap an error from reading or writing `path`,
+    /// telling apart a lack of permission from other failures.
+    pub(crate) fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        let path = path.into();
+        match source.kind() {
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied { path },
+            _ => Self::Io { path, source },
+        }
+    }
+
+    pub(crate) fn invalid_value(param: Param, reason: impl Into<String>) -> Self {
+        Self::InvalidValue {
+            param,
+            reason: reason.into(),
+        }
+    }
+
+    pub(crate) fn parse(path: impl Into<PathBuf>, reason: impl Display) -> Self {
+        Self::Parse {
+            path: path.into(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ModuleNotLoaded { module_path } => write!(
+                f,
+                "the maccel kernel module is not loaded, there's no {}",
+                module_path.display()
+            ),
+            Error::UnsupportedParameter { name } => write!(
+                f,
+                "the loaded kernel module has no parameter {name}, it might be an older version of maccel"
+            ),
+            Error::PermissionDenied { path } if is_for_maccel_group(path) => write!(
+                f,
+                "permission denied for {}, add yourself to the maccel group with `usermod -aG maccel $USER`, then log back in, or run as root",
+                path.display()
+            ),
+            Error::PermissionDenied { path } => {
+                write!(f, "permission denied for {}", path.display())
+            }
+            Error::InvalidValue { param, reason } => {
+                write!(f, "invalid value for {}: {reason}", param.name())
+            }
+            Error::InvalidCurve(issues) => {
+                let issues: Vec<_> = issues.iter().map(|i| i.to_string()).collect();
+                write!(f, "{}", issues.join("; "))
+            }
+            Error::UnknownMode(mode) => write!(f, "unknown acceleration mode {mode:?}"),
+            Error::Parse { path, reason } => {
+                write!(f, "couldn't interpret {}: {reason}", path.display())
+            }
+            Error::InvalidNumber(number) => write!(f, "{number:?} is not a valid number"),
+            Error::InvalidLookupTable(reason) => write!(f, "invalid look-up table: {reason}"),
+            Error::UnknownProfile(name) => write!(f, "no such profile {name:?}"),
+            Error::InvalidProfileName(name) => write!(f, "invalid profile name {name:?}"),
+            Error::ConfigDirNotFound => write!(
+                f,
+                "couldn't find the config directory, neither XDG_CONFIG_HOME nor HOME is set"
+            ),
+            Error::RollbackFailed { source, failures } => {
+                let failures: Vec<_> = failures
+                    .iter()
+                    .map(|(name, err)| format!("{name} ({err})"))
+                    .collect();
+                write!(
+                    f,
+                    "{source}, and failed to roll back {}",
+                    failures.join(", ")
+                )
+            }
+            Error::Io { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::RollbackFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the maccel group is what gives access to `path`: the kernel module's parameters,
+/// and its device, as set up by the install script, not e.g. /etc/modprobe.d or profiles.
+fn is_for_maccel_group(path: &Path) -> bool {
+    path.ancestors().any(|dir| dir.ends_with("sys/module")) || path.ends_with("dev/maccel")
+}
+
+#[cfg(test)]
+#[test]
+fn permission_denied_suggests_the_maccel_group_only_where_it_helps() {
+    let message = |path: &str| {
+        Error::PermissionDenied {
+            path: PathBuf::from(path),
+        }
+        .to_string()
+    };
+
+    assert!(message("/sys/module/maccel/parameters/ACCEL").contains("maccel group"));
+    assert!(message("/dev/maccel").contains("maccel group"));
+    assert_eq!(
+        message("/etc/modprobe.d/maccel.conf"),
+        "permission denied for /etc/modprobe.d/maccel.conf"
+    );
+    assert!(!message("/home/me/.config/maccel/profiles/fast.toml").contains("maccel group"));
+
+    let rolled_back = Error::RollbackFailed {
+        source: Box::new(Error::UnknownMode("fast".to_string())),
+        failures: vec![],
+    };
+    let source = std::error::Error::source(&rolled_back).map(ToString::to_string);
+    assert_eq!(
+        source.as_deref(),
+        Some("unknown acceleration mode \"fast\"")
+    );
+}
@@ -7,45 +7,45 @@
 use std::{
     fs,
     io::Read,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
     thread::{self, JoinHandle},
 };
 
-static mut INPUT_SPEED: f64 = 0.0;
+use crate::{Error, libmaccel::fixedptc::Fpt};
 
-use anyhow::Context;
-
-use crate::libmaccel::fixedptc::Fpt;
+/// The bits of the last input speed read, as an `f64`, so any thread can read it.
+static INPUT_SPEED: AtomicU64 = AtomicU64::new(0);
 
 pub fn read_input_speed() -> f64 {
-    // Safety don't care about race conditions
-    unsafe { INPUT_SPEED }
+    f64::from_bits(INPUT_SPEED.load(Ordering::Relaxed))
+}
+
+/// The character device the driver echoes the input speed on,
+/// resolved under `root` instead of `/`.
+pub fn device_path(root: impl AsRef<Path>) -> PathBuf {
+    root.as_ref().join("dev/maccel")
 }
 
-pub fn setup_input_speed_reader() -> JoinHandle<anyhow::Result<()>> {
-    thread::spawn(|| {
-        let mut file = fs::File::open("/dev/maccel").context("failed to open /dev/maccel")?;
+pub fn setup_input_speed_reader(device_path: PathBuf) -> JoinHandle<crate::Result<()>> {
+    thread::spawn(move || {
+        let mut file = fs::File::open(&device_path).map_err(|err| Error::io(&device_path, err))?;
         let mut buffer = [0u8; 8];
 
         loop {
             let nread = file
                 .read(&mut buffer)
-                .expect("failed to read bytes from /dev/maccel");
-
-            let num = match nread {
-                4 => {
-                    let buffer = buffer
-                        .first_chunk::<4>()
-                        .expect("failed to grab 4 bytes from the read buffer");
-                    i32::from_be_bytes(*buffer) as i64
-                }
-                8 => i64::from_be_bytes(buffer),
+                .map_err(|err| Error::io(&device_path, err))?;
+
+            let num = match (nread, buffer.first_chunk::<4>()) {
+                (4, Some(buffer)) => i32::from_be_bytes(*buffer) as i64,
+                (8, _) => i64::from_be_bytes(buffer),
                 _ => 0,
             };
 
             let num: f64 = Fpt(num).into();
 
-            // Safety don't care about race conditions
-            unsafe { INPUT_SPEED = num };
+            INPUT_SPEED.store(num.to_bits(), Ordering::Relaxed);
 
             thread::sleep(std::time::Duration::from_nanos(500));
         }
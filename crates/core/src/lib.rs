@@ -1,10 +1,21 @@
 mod context;
+mod curve;
+mod error;
 pub mod inputspeed;
 mod libmaccel;
+pub mod lint;
+mod lut;
 mod params;
+pub mod persist;
+pub mod precision;
 mod sens_fns;
+pub mod units;
+pub mod validation;
 
 pub use context::*;
+pub use curve::*;
+pub use error::{Error, Result};
 pub use libmaccel::fixedptc;
+pub use lut::*;
 pub use params::*;
 pub use sens_fns::*;
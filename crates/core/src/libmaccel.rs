@@ -4,14 +4,82 @@ pub mod fixedptc {
         str::FromStr,
     };
 
-    use anyhow::Context;
-
     use super::c_libmaccel::{self, str_to_fpt};
 
+    /// The width of the fixed-point numbers, as the driver is built for this architecture.
+    #[cfg(fixedpt_bits = "32")]
+    pub const FIXEDPT_BITS: u32 = 32;
+    #[cfg(fixedpt_bits = "64")]
+    pub const FIXEDPT_BITS: u32 = 64;
+
+    /// How many of the bits are for the whole part of the number, as in `fixedptc.h`
+    pub const FIXEDPT_WBITS: u32 = FIXEDPT_BITS / 2;
+
+    /// The widths of fixed-point numbers the driver can be built with.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FixedptWidth {
+        W32,
+        W64,
+    }
+
+    impl FixedptWidth {
+        pub const ALL: [Self; 2] = [Self::W32, Self::W64];
+
+        /// The width this crate, and the driver for this architecture, is built for.
+        #[cfg(fixedpt_bits = "32")]
+        pub const CURRENT: Self = Self::W32;
+        #[cfg(fixedpt_bits = "64")]
+        pub const CURRENT: Self = Self::W64;
+
+        pub fn bits(&self) -> u32 {
+            match self {
+                Self::W32 => 32,
+                Self::W64 => 64,
+            }
+        }
+    }
+
     #[derive(Debug, Default, Clone, Copy, PartialEq)]
     #[repr(transparent)]
     pub struct Fpt(pub i64);
 
+    impl Fpt {
+        /// Convert to a fixed-point number, unless the value is too big, or too small
+        /// (but not zero), to be represented with [`FIXEDPT_BITS`].
+        pub fn checked_from(value: f64) -> Option<Self> {
+            let bound = (1u64 << (FIXEDPT_WBITS - 1)) as f64;
+            if !value.is_finite() || value >= bound || value < -bound {
+                return None;
+            }
+
+            let fpt = Self::from(value);
+            if fpt.0 == 0 && value != 0.0 {
+                return None;
+            }
+
+            Some(fpt)
+        }
+
+        /// The number as the driver prints it, with as many decimals as it has.
+        /// Unlike converting to a `&str`, which borrows the static buffer of the driver's
+        /// `fptoa`, it's safe to call from any thread.
+        pub fn to_driver_string(&self) -> String {
+            super::pure::fptoa(*self)
+        }
+    }
+
+    #[cfg(test)]
+    #[test]
+    fn fpt_checked_conversion_rejects_unrepresentable_values() {
+        let bound = (1u64 << (FIXEDPT_WBITS - 1)) as f64;
+
+        assert_eq!(Fpt::checked_from(1.5), Some(Fpt::from(1.5)));
+        assert_eq!(Fpt::checked_from(-bound), Some(Fpt::from(-bound)));
+        assert_eq!(Fpt::checked_from(bound), None);
+        assert_eq!(Fpt::checked_from(f64::NAN), None);
+        assert_eq!(Fpt::checked_from(1e-12), None);
+    }
+
     impl From<Fpt> for f64 {
         fn from(value: Fpt) -> Self {
             unsafe { c_libmaccel::fpt_to_float(value) }
@@ -42,23 +110,61 @@ pub mod fixedptc {
         assert_for!(0.5);
     }
 
+    /// Serialized as the float it stands for, so it doesn't depend on [`FIXEDPT_BITS`].
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for Fpt {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_f64((*self).into())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for Fpt {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let value = f64::deserialize(deserializer)?;
+            Fpt::checked_from(value).ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "{value} cannot be represented as a {FIXEDPT_BITS}-bit fixed-point number"
+                ))
+            })
+        }
+    }
+
+    /// The string borrows a buffer that the next conversion, from any thread, overwrites.
+    /// Prefer [`Fpt::to_driver_string`].
     impl<'a> TryFrom<&'a Fpt> for &'a str {
-        type Error = anyhow::Error;
+        type Error = crate::Error;
 
         fn try_from(value: &'a Fpt) -> Result<Self, Self::Error> {
             unsafe {
                 let s = CStr::from_ptr(c_libmaccel::fpt_to_str(*value));
-                let s = core::str::from_utf8(s.to_bytes())?;
+                let s = core::str::from_utf8(s.to_bytes()).map_err(|_| {
+                    crate::Error::InvalidNumber(String::from_utf8_lossy(s.to_bytes()).into())
+                })?;
                 Ok(s)
             }
         }
     }
 
+    #[cfg(test)]
+    #[test]
+    fn fpt_to_driver_string_is_fptoa() {
+        for value in [0.0, 1.0, -1.5, 0.1, 1000.0, 1e-9, -12345.6789] {
+            let fpt = Fpt::from(value);
+            let c_string: &str = (&fpt).try_into().unwrap();
+            assert_eq!(fpt.to_driver_string(), c_string, "{value}");
+        }
+        assert_eq!(
+            Fpt::from(1.5).to_driver_string().trim_end_matches('0'),
+            "1.5"
+        );
+    }
+
     impl FromStr for Fpt {
-        type Err = anyhow::Error;
+        type Err = crate::Error;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let cstr = CString::new(s).context("Failed to convert to a C string")?;
+            let cstr = CString::new(s).map_err(|_| crate::Error::InvalidNumber(s.to_string()))?;
             let f = unsafe { str_to_fpt(cstr.as_ptr()) };
             Ok(f)
         }
@@ -71,13 +177,16 @@ pub struct Vector {
     pub y: i64,
 }
 
+#[cfg(not(feature = "pure_rust"))]
 mod c_libmaccel {
     use super::{fixedptc, Vector};
     use crate::params::AccelParams;
     use std::ffi::c_char;
 
+    // The look-up table's `Box` is as a pointer to it, like the driver takes it.
+    #[allow(improper_ctypes)]
     unsafe extern "C" {
-        pub fn sensitivity_rs(speed_in: fixedptc::Fpt, args: AccelParams) -> Vector;
+        pub fn sensitivity_rs(speed_in: fixedptc::Fpt, args: &AccelParams) -> Vector;
     }
 
     unsafe extern "C" {
@@ -88,4 +197,10 @@ mod c_libmaccel {
     }
 }
 
+mod pure;
+#[cfg(feature = "pure_rust")]
+use pure as c_libmaccel;
+
+pub(crate) use pure::sensitivity_for_width;
+
 pub use c_libmaccel::sensitivity_rs;
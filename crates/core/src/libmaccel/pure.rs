@@ -0,0 +1,770 @@
+//! What `libmaccel.c` exposes of the driver, implemented in Rust with the `pure_rust` feature,
+//! so that the crate builds without a C compiler.
+//!
+//! It's a port of the driver's headers that computes the exact same numbers,
+//! checked against the driver's own snapshot tests, see `driver/tests/accel.test.c`.
+//! The functions are `unsafe` only to be drop-in replacements for the C ones.
+//!
+//! It's also how we compute the curves for the width of fixed-point numbers
+//! this crate isn't built for, see [`sensitivity_for_width`].
+
+mod fixedpt;
+
+use std::f64::consts::{LN_2, TAU};
+#[cfg(feature = "pure_rust")]
+use std::ffi::{CStr, c_char};
+
+use fixedpt::{Fixed, Fp32, Fp64, abs, div, exp, ln, mul, pow, tanh, toint};
+
+#[cfg(feature = "pure_rust")]
+use super::Vector;
+use super::fixedptc::{FixedptWidth, Fpt};
+use crate::{
+    AccelMode, AllParamArgs, LUT_MAX_POINTS, LookupTableCurveParams,
+    params::{
+        AccelParams, AccelParamsByMode, ClassicCurveParams, JumpCurveParams, LinearCurveParams,
+        MacosCurveParams, NaturalCurveParams, PowerCurveParams, SynchronousCurveParams,
+        WindowsCurveParams,
+    },
+    reference::{WINDOWS_CURVE, WINDOWS_SLIDER_MULTIPLIERS},
+};
+use fixedpt::strings::Fptoa;
+
+/// The width the driver is built with for this architecture.
+#[cfg(all(feature = "pure_rust", fixedpt_bits = "32"))]
+type Fp = Fp32;
+#[cfg(all(feature = "pure_rust", fixedpt_bits = "64"))]
+type Fp = Fp64;
+
+/// Like `fptoa`'s, where the last number converted to a string is kept.
+#[cfg(feature = "pure_rust")]
+static mut FPTOA_BUFFER: [u8; 25] = [0; 25];
+
+#[cfg(feature = "pure_rust")]
+pub unsafe fn sensitivity_rs(speed_in: Fpt, args: &AccelParams) -> Vector {
+    let (x, y) = sensitivity::<Fp>(fp(speed_in), args);
+    Vector {
+        x: fpt(x).0,
+        y: fpt(y).0,
+    }
+}
+
+/// Like `fptoa`, the string is only valid until the next call.
+#[cfg(feature = "pure_rust")]
+pub unsafe fn fpt_to_str(num: Fpt) -> *const c_char {
+    let string = fp::<Fp>(num).fptoa();
+    let buffer = &raw mut FPTOA_BUFFER;
+    unsafe {
+        let buffer = buffer.cast::<u8>();
+        std::ptr::copy_nonoverlapping(string.as_ptr(), buffer, string.len());
+        buffer.add(string.len()).write(0);
+        buffer.cast()
+    }
+}
+
+#[cfg(feature = "pure_rust")]
+pub unsafe fn str_to_fpt(string: *const c_char) -> Fpt {
+    let string = unsafe { CStr::from_ptr(string) };
+    fpt(fixedpt::strings::atofp::<Fp>(string.to_bytes()))
+}
+
+#[cfg(feature = "pure_rust")]
+pub unsafe fn fpt_to_float(value: Fpt) -> f64 {
+    fp::<Fp>(value).todouble()
+}
+
+#[cfg(feature = "pure_rust")]
+pub unsafe fn fpt_from_float(value: f64) -> Fpt {
+    fpt(Fp::rconst(value))
+}
+
+/// What `fptoa` gives for the number, on the width this crate is built for,
+/// without `fptoa`'s static buffer.
+pub(crate) fn fptoa(num: Fpt) -> String {
+    match FixedptWidth::CURRENT {
+        FixedptWidth::W32 => fp::<Fp32>(num).fptoa(),
+        FixedptWidth::W64 => fp::<Fp64>(num).fptoa(),
+    }
+}
+
+/// The sensitivity as the driver computes it when it's built for `width`, with `params`
+/// as they are for this build's width, converted like the CLI would for the other one.
+pub(crate) fn sensitivity_for_width(
+    width: FixedptWidth,
+    speed_in: f64,
+    mode: AccelMode,
+    params: &AllParamArgs,
+) -> (f64, f64) {
+    fn compute<F: Fixed>(speed_in: f64, mode: AccelMode, params: &AllParamArgs) -> (f64, f64) {
+        let converted = AllParamArgs::from_fn(|p| fpt(F::rconst(f64::from(params.get(p)))));
+        let mut args = converted.convert_to_accel_args(mode);
+        if let AccelParamsByMode::LookupTable(table) = &mut args.by_mode {
+            for (idx, &(speed, sens)) in params.lookup_table.points.points().iter().enumerate() {
+                table.speeds[idx] = fpt(F::rconst(speed));
+                table.sens[idx] = fpt(F::rconst(sens));
+            }
+        }
+
+        let (x, y) = sensitivity(F::rconst(speed_in), &args);
+        (x.todouble(), y.todouble())
+    }
+
+    match width {
+        FixedptWidth::W32 => compute::<Fp32>(speed_in, mode, params),
+        FixedptWidth::W64 => compute::<Fp64>(speed_in, mode, params),
+    }
+}
+
+fn fp<F: Fixed>(value: Fpt) -> F {
+    F::from_raw(value.0)
+}
+
+fn fpt<F: Fixed>(value: F) -> Fpt {
+    Fpt(value.to_raw())
+}
+
+/// `sensitivity` in `accel.h`
+fn sensitivity<F: Fixed>(input_speed: F, args: &AccelParams) -> (F, F) {
+    let sens = match &args.by_mode {
+        AccelParamsByMode::Linear(args) => linear_sens_fun(input_speed, args),
+        AccelParamsByMode::Classic(args) => classic_sens_fun(input_speed, args),
+        AccelParamsByMode::Natural(args) => natural_sens_fun(input_speed, args),
+        AccelParamsByMode::Synchronous(args) => synchronous_sens_fun(input_speed, args),
+        AccelParamsByMode::Jump(args) => jump_sens_fun(input_speed, args),
+        AccelParamsByMode::Power(args) => power_sens_fun(input_speed, args),
+        AccelParamsByMode::Windows(windows) => {
+            windows_sens_fun(input_speed, fp(args.input_dpi), windows)
+        }
+        AccelParamsByMode::Macos(args) => macos_sens_fun(input_speed, args),
+        AccelParamsByMode::LookupTable(args) => lut_sens_fun(input_speed, args),
+    };
+    let sens = mul(sens, fp::<F>(args.sens_mult));
+    (sens, mul(sens, fp::<F>(args.yx_ratio)))
+}
+
+/// The end of the linear and classic sensitivity functions, capping the output.
+fn with_output_cap<F: Fixed>(mut sens: F, output_cap: F) -> F {
+    let mut sign = F::ONE;
+    if output_cap > F::ZERO {
+        let mut cap = output_cap - F::ONE;
+        if cap < F::ZERO {
+            cap = -cap;
+            sign = -sign;
+        }
+        sens = sens.min(cap);
+    }
+
+    F::ONE + mul(sign, sens)
+}
+
+/// `__linear_sens_fun` in `accel/linear.h`
+fn linear_sens_fun<F: Fixed>(input_speed: F, args: &LinearCurveParams) -> F {
+    let accel = fp::<F>(args.accel_linear);
+    let offset = fp::<F>(args.offset_linear);
+
+    if input_speed <= offset {
+        return F::ONE;
+    }
+
+    let x = input_speed - offset;
+    let sens = mul(accel, div(mul(x, x), input_speed));
+
+    with_output_cap(sens, fp::<F>(args.output_cap_linear))
+}
+
+/// `__classic_sens_fun` in `accel/classic.h`
+fn classic_sens_fun<F: Fixed>(input_speed: F, args: &ClassicCurveParams) -> F {
+    let offset = fp::<F>(args.offset_classic);
+
+    if input_speed <= offset {
+        return F::ONE;
+    }
+
+    let sens = div(
+        pow(
+            mul(fp::<F>(args.accel_classic), input_speed - offset),
+            fp::<F>(args.power_classic),
+        ),
+        input_speed,
+    );
+
+    with_output_cap(sens, fp::<F>(args.output_cap_classic))
+}
+
+/// `__natural_sens_fun` in `accel/natural.h`
+fn natural_sens_fun<F: Fixed>(input_speed: F, args: &NaturalCurveParams) -> F {
+    let decay_rate = fp::<F>(args.decay_rate);
+    let offset = fp::<F>(args.offset_natural);
+    let limit = fp::<F>(args.limit);
+
+    if input_speed <= offset || limit <= F::ONE || decay_rate <= F::ZERO {
+        return F::ONE;
+    }
+
+    let limit = limit - F::ONE;
+    let accel = div(decay_rate, abs(limit));
+    let constant = div(-limit, accel);
+
+    let offset_x = offset - input_speed;
+    let decay = exp(mul(accel, offset_x));
+
+    let output_denom = div(decay, accel) - offset_x;
+    let output = mul(limit, output_denom) + constant;
+
+    div(output, input_speed) + F::ONE
+}
+
+/// `__synchronous_sens_fun` in `accel/synchronous.h`
+fn synchronous_sens_fun<F: Fixed>(input_speed: F, args: &SynchronousCurveParams) -> F {
+    let motivity = fp::<F>(args.motivity);
+    let smooth = fp::<F>(args.smooth);
+    let sync_speed = fp::<F>(args.sync_speed);
+
+    let log_motivity = ln(motivity);
+    let gamma_const = div(fp::<F>(args.gamma), log_motivity);
+    let log_syncspeed = ln(sync_speed);
+    let sharpness = if smooth == F::ZERO {
+        F::rconst(16.0)
+    } else {
+        div(F::rconst(0.5), smooth)
+    };
+    let use_linear_clamp = sharpness >= F::rconst(16.0);
+    let sharpness_recip = div(F::ONE, sharpness);
+    let minimum_sens = div(F::ONE, motivity);
+    let maximum_sens = motivity;
+
+    // if sharpness >= 16, use linear clamp for activation function.
+    if use_linear_clamp {
+        let log_space = mul(gamma_const, ln(input_speed) - log_syncspeed);
+
+        if log_space < -F::ONE {
+            return minimum_sens;
+        }
+
+        if log_space > F::ONE {
+            return maximum_sens;
+        }
+
+        return exp(mul(log_space, log_motivity));
+    }
+
+    if input_speed == sync_speed {
+        return F::ONE;
+    }
+
+    let log_x = ln(input_speed);
+    let log_diff = log_x - log_syncspeed;
+
+    let exponent = if log_diff > F::ZERO {
+        let log_space = mul(gamma_const, log_diff);
+        pow(tanh(pow(log_space, sharpness)), sharpness_recip)
+    } else {
+        let log_space = mul(-gamma_const, log_diff);
+        -pow(tanh(pow(log_space, sharpness)), sharpness_recip)
+    };
+    exp(mul(exponent, log_motivity))
+}
+
+/// `__jump_sens_fun` in `accel/jump.h`
+fn jump_sens_fun<F: Fixed>(input_speed: F, args: &JumpCurveParams) -> F {
+    let speed = fp::<F>(args.jump_speed);
+    let sens = fp::<F>(args.jump_sens);
+
+    let width = mul(fp::<F>(args.smooth_jump), speed);
+    if width < F::ONE {
+        return if input_speed < speed { F::ONE } else { sens };
+    }
+
+    let distance = input_speed - speed;
+    // `JUMP_TRANSITION_END`
+    let transition_end = mul(F::rconst(F::FBITS as f64 * LN_2 / TAU), width);
+    if distance >= transition_end {
+        return sens;
+    }
+    if distance <= -transition_end {
+        return F::ONE;
+    }
+
+    let rate = div(F::rconst(TAU), width);
+    let decay = exp(-mul(rate, abs(distance)));
+    let logistic = if distance >= F::ZERO {
+        div(F::ONE, F::ONE + decay)
+    } else {
+        div(decay, F::ONE + decay)
+    };
+
+    F::ONE + mul(sens - F::ONE, logistic)
+}
+
+/// `power_fn` in `accel/power.h`
+fn power_fn<F: Fixed>(base: F, exponent: F) -> F {
+    if base >= F::ONE {
+        return pow(base, exponent);
+    }
+
+    if base <= F::from_raw(2) {
+        return F::ZERO;
+    }
+
+    let log_inverse = mul(ln(div(F::ONE, base)), exponent);
+    // `POWER_MAX_LOG`
+    if log_inverse >= F::rconst((F::WIDTH as usize - F::FBITS - 2) as f64 * LN_2) {
+        return F::ZERO;
+    }
+
+    div(F::ONE, exp(log_inverse))
+}
+
+/// `__power_sens_fun` in `accel/power.h`
+fn power_sens_fun<F: Fixed>(input_speed: F, args: &PowerCurveParams) -> F {
+    let mut sens = fp::<F>(args.output_offset_power);
+
+    let base = mul(fp::<F>(args.scale_power), input_speed);
+    if base > F::ZERO {
+        sens += power_fn(base, fp::<F>(args.exponent_power));
+    }
+
+    let output_cap = fp::<F>(args.output_cap_power);
+    if output_cap > F::ZERO {
+        sens = sens.min(output_cap);
+    }
+
+    sens
+}
+
+/// `__windows_sens_fun` in `accel/windows.h`
+fn windows_sens_fun<F: Fixed>(input_speed: F, input_dpi: F, args: &WindowsCurveParams) -> F {
+    // `WINDOWS_CURVE_MOUSE_SPEEDS` and `WINDOWS_CURVE_POINTER_SPEEDS`
+    let mouse_speeds = WINDOWS_CURVE.map(|(mouse, _)| F::rconst(mouse));
+    let pointer_speeds = WINDOWS_CURVE.map(|(_, pointer)| F::rconst(pointer));
+
+    // `WINDOWS_MOUSE_DPI` and `WINDOWS_FRAME_RATE`
+    let counts = div(input_dpi, F::rconst(400.0));
+    let mouse_speed = mul(
+        mul(input_speed, counts),
+        div(F::rconst(60.0), fp::<F>(args.refresh_rate_windows)),
+    );
+
+    // `FIXEDPT_ONE_HALF`
+    let half = F::from_raw(F::ONE.to_raw() >> 1);
+    let notch = toint(fp::<F>(args.slider_windows) + half)
+        .clamp(1, WINDOWS_SLIDER_MULTIPLIERS.len() as i32);
+
+    let mut i = 1;
+    while i < WINDOWS_CURVE.len() - 1 && mouse_speed > mouse_speeds[i] {
+        i += 1;
+    }
+
+    let slope = div(
+        pointer_speeds[i] - pointer_speeds[i - 1],
+        mouse_speeds[i] - mouse_speeds[i - 1],
+    );
+
+    let mut gain = slope;
+    if i > 1 {
+        let intercept = pointer_speeds[i - 1] - mul(slope, mouse_speeds[i - 1]);
+        gain += div(intercept, mouse_speed);
+    }
+
+    // `WINDOWS_SLIDER_MULTIPLIERS` and `WINDOWS_NORMALIZED_DPI`
+    let multiplier = F::rconst(WINDOWS_SLIDER_MULTIPLIERS[notch as usize - 1]);
+    mul(
+        mul(gain, multiplier),
+        mul(
+            div(fp::<F>(args.screen_dpi_windows), F::rconst(1000.0)),
+            counts,
+        ),
+    )
+}
+
+/// `__macos_sens_fun` in `accel/macos.h`
+fn macos_sens_fun<F: Fixed>(input_speed: F, args: &MacosCurveParams) -> F {
+    // `MACOS_RAMP_SPEED`
+    let u = div(input_speed, F::rconst(8.0));
+    let ramp = if u < F::ONE {
+        let u_squared = mul(u, u);
+        div(u_squared, F::ONE + u_squared)
+    } else {
+        let inverse = div(F::ONE, u);
+        div(F::ONE, F::ONE + mul(inverse, inverse))
+    };
+
+    F::ONE + mul(fp::<F>(args.tracking_speed), ramp)
+}
+
+/// `__lut_sens_fun` in `accel/lut.h`
+fn lut_sens_fun<F: Fixed>(input_speed: F, args: &LookupTableCurveParams) -> F {
+    let len = (args.len as usize).min(LUT_MAX_POINTS);
+    let speed = |idx: usize| fp::<F>(args.speeds[idx]);
+    let sens = |idx: usize| fp::<F>(args.sens[idx]);
+
+    if len == 0 {
+        return F::ONE;
+    }
+
+    if input_speed <= speed(0) {
+        return sens(0);
+    }
+
+    for idx in 1..len {
+        if input_speed <= speed(idx) {
+            let t = div(input_speed - speed(idx - 1), speed(idx) - speed(idx - 1));
+            return sens(idx - 1) + mul(t, sens(idx) - sens(idx - 1));
+        }
+    }
+
+    sens(len - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use fixedpt::{
+        fromint, sqrt,
+        strings::{Fptoa, atofp},
+    };
+
+    /// The snapshots are for the 64-bit width, whichever this crate is built for.
+    type Fp = Fp64;
+
+    fn from_float(value: f64) -> Fpt {
+        fpt(Fp::rconst(value))
+    }
+
+    fn snapshot(name: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../driver/tests/snapshots")
+            .join(name);
+        fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+    }
+
+    /// `input_speed` in `speed.h`
+    fn input_speed(dx: Fp, dy: Fp, time_ms: Fp) -> Fp {
+        let distance = sqrt(mul(dx, dx) + mul(dy, dy));
+        if distance == Fp::from_raw(-1) {
+            return Fp::ZERO;
+        }
+        div(distance, time_ms)
+    }
+
+    /// `test_acceleration` in `accel.test.c`, with `f_accelerate` carrying the remainders
+    /// from one input to the next, and from one test to the next as it's `static` there.
+    fn assert_acceleration_snapshot(name: &str, args: AccelParams, carry: &mut (Fp, Fp)) {
+        let normalized_dpi = fromint::<Fp>(1000);
+        let mut content = String::new();
+
+        for x in -128..127 {
+            for y in -128..127 {
+                let dpi_factor = div(normalized_dpi, fp(args.input_dpi));
+                let dx = mul(fromint(x), dpi_factor);
+                let dy = mul(fromint(y), dpi_factor);
+
+                let (sens_x, sens_y) = sensitivity(input_speed(dx, dy, Fp::ONE), &args);
+
+                let dx_out = mul(dx, sens_x) + carry.0;
+                let dy_out = mul(dy, sens_y) + carry.1;
+                let (x_out, y_out) = (toint(dx_out), toint(dy_out));
+                *carry = (dx_out - fromint::<Fp>(x_out), dy_out - fromint::<Fp>(y_out));
+
+                content.push_str(&format!("({x}, {y}) => ({x_out}, {y_out})\n"));
+            }
+        }
+
+        assert!(content == snapshot(name), "{name} doesn't match");
+    }
+
+    fn args(sens_mult: f64, yx_ratio: f64, by_mode: AccelParamsByMode) -> AccelParams {
+        AccelParams {
+            sens_mult: from_float(sens_mult),
+            yx_ratio: from_float(yx_ratio),
+            input_dpi: from_float(1000.0),
+            by_mode,
+        }
+    }
+
+    fn linear(accel: f64, offset: f64, output_cap: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Linear(LinearCurveParams {
+            accel_linear: from_float(accel),
+            offset_linear: from_float(offset),
+            output_cap_linear: from_float(output_cap),
+        })
+    }
+
+    fn classic(accel: f64, power: f64, offset: f64, output_cap: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Classic(ClassicCurveParams {
+            accel_classic: from_float(accel),
+            power_classic: from_float(power),
+            offset_classic: from_float(offset),
+            output_cap_classic: from_float(output_cap),
+        })
+    }
+
+    fn natural(decay_rate: f64, offset: f64, limit: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Natural(NaturalCurveParams {
+            decay_rate: from_float(decay_rate),
+            offset_natural: from_float(offset),
+            limit: from_float(limit),
+        })
+    }
+
+    fn jump(speed: f64, sens: f64, smooth: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Jump(JumpCurveParams {
+            jump_speed: from_float(speed),
+            jump_sens: from_float(sens),
+            smooth_jump: from_float(smooth),
+        })
+    }
+
+    fn power(scale: f64, exponent: f64, output_offset: f64, cap: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Power(PowerCurveParams {
+            scale_power: from_float(scale),
+            exponent_power: from_float(exponent),
+            output_offset_power: from_float(output_offset),
+            output_cap_power: from_float(cap),
+        })
+    }
+
+    fn windows(slider: f64, refresh_rate: f64, screen_dpi: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Windows(WindowsCurveParams {
+            slider_windows: from_float(slider),
+            refresh_rate_windows: from_float(refresh_rate),
+            screen_dpi_windows: from_float(screen_dpi),
+        })
+    }
+
+    fn macos(tracking_speed: f64) -> AccelParamsByMode {
+        AccelParamsByMode::Macos(MacosCurveParams {
+            tracking_speed: from_float(tracking_speed),
+        })
+    }
+
+    fn lookup_table(points: &[(f64, f64)]) -> AccelParamsByMode {
+        let mut table = LookupTableCurveParams {
+            len: points.len() as _,
+            speeds: [Fpt(0); LUT_MAX_POINTS],
+            sens: [Fpt(0); LUT_MAX_POINTS],
+        };
+        for (idx, &(speed, sens)) in points.iter().enumerate() {
+            table.speeds[idx] = from_float(speed);
+            table.sens[idx] = from_float(sens);
+        }
+        AccelParamsByMode::LookupTable(Box::new(table))
+    }
+
+    #[test]
+    fn curves_match_the_drivers_snapshots() {
+        let cases = [
+            (
+                "SENS_MULT-1-ACCEL-0-OFFSET0-OUTPUT_CAP-0.snapshot",
+                args(1.0, 1.0, linear(0.0, 0.0, 0.0)),
+            ),
+            (
+                "SENS_MULT-1-ACCEL-0.3-OFFSET2-OUTPUT_CAP-2.snapshot",
+                args(1.0, 1.0, linear(0.3, 2.0, 2.0)),
+            ),
+            (
+                "SENS_MULT-0.1325-ACCEL-0.3-OFFSET21.333333-OUTPUT_CAP-2.snapshot",
+                args(0.1325, 1.0, linear(0.3, 21.333333, 2.0)),
+            ),
+            (
+                "SENS_MULT-0.1875-ACCEL-0.05625-OFFSET10.6666666-OUTPUT_CAP-2.snapshot",
+                args(0.1875, 1.0, linear(0.05625, 10.6666666, 2.0)),
+            ),
+            (
+                "SENS_MULT-0.0917-ACCEL-0.002048-OFFSET78.125-OUTPUT_CAP-2.0239.snapshot",
+                args(0.0917, 1.0, linear(0.002048, 78.125, 2.0239)),
+            ),
+            (
+                "SENS_MULT-0.07-ACCEL-0.055-OFFSET21-OUTPUT_CAP-3.snapshot",
+                args(0.07, 1.15, linear(0.055, 21.0, 3.0)),
+            ),
+            (
+                "Natural__SENS_MULT-1-DECAY_RATE-0-OFFSET0-LIMIT-0.snapshot",
+                args(1.0, 1.0, natural(0.0, 0.0, 0.0)),
+            ),
+            (
+                "Natural__SENS_MULT-1-DECAY_RATE-0.1-OFFSET0-LIMIT-0.snapshot",
+                args(1.0, 1.0, natural(0.1, 0.0, 0.0)),
+            ),
+            (
+                "Natural__SENS_MULT-1-DECAY_RATE-0.1-OFFSET8-LIMIT-0.snapshot",
+                args(1.0, 1.0, natural(0.1, 8.0, 0.0)),
+            ),
+            (
+                "Natural__SENS_MULT-1-DECAY_RATE-0.03-OFFSET8-LIMIT-1.5.snapshot",
+                args(1.0, 1.0, natural(0.03, 8.0, 1.5)),
+            ),
+            (
+                "Synchronous__SENS_MULT-1-GAMMA-0.8-SMOOTH0.5-MOTIVITY-1.5-SYNC_SPEED-32.snapshot",
+                args(
+                    1.0,
+                    1.15,
+                    AccelParamsByMode::Synchronous(SynchronousCurveParams {
+                        gamma: from_float(0.8),
+                        smooth: from_float(0.5),
+                        motivity: from_float(1.5),
+                        sync_speed: from_float(32.0),
+                    }),
+                ),
+            ),
+            (
+                "LookupTable__SENS_MULT-1-POINTS-2_1-8_1.5-32_2.5.snapshot",
+                args(
+                    1.0,
+                    1.15,
+                    lookup_table(&[(2.0, 1.0), (8.0, 1.5), (32.0, 2.5)]),
+                ),
+            ),
+            (
+                "Jump__SENS_MULT-1-SPEED-15-SENS-1.5-SMOOTH0.snapshot",
+                args(1.0, 1.0, jump(15.0, 1.5, 0.0)),
+            ),
+            (
+                "Jump__SENS_MULT-1-SPEED-15-SENS-1.5-SMOOTH0.5.snapshot",
+                args(1.0, 1.15, jump(15.0, 1.5, 0.5)),
+            ),
+            (
+                "Power__SENS_MULT-1-SCALE-1-EXPONENT-0.05-OUTPUT_OFFSET-0-OUTPUT_CAP-0.snapshot",
+                args(1.0, 1.0, power(1.0, 0.05, 0.0, 0.0)),
+            ),
+            (
+                "Power__SENS_MULT-0.5-SCALE-0.1-EXPONENT-0.4-OUTPUT_OFFSET-1-OUTPUT_CAP-2.5.snapshot",
+                args(0.5, 1.15, power(0.1, 0.4, 1.0, 2.5)),
+            ),
+            (
+                "Windows__SENS_MULT-1-SLIDER-6-REFRESH_RATE-60-SCREEN_DPI-96.snapshot",
+                args(1.0, 1.0, windows(6.0, 60.0, 96.0)),
+            ),
+            (
+                "Windows__SENS_MULT-2-SLIDER-8-REFRESH_RATE-144-SCREEN_DPI-120.snapshot",
+                args(2.0, 1.15, windows(8.0, 144.0, 120.0)),
+            ),
+            (
+                "Macos__SENS_MULT-1-TRACKING_SPEED-1.snapshot",
+                args(1.0, 1.0, macos(1.0)),
+            ),
+            (
+                "Macos__SENS_MULT-0.5-TRACKING_SPEED-3.snapshot",
+                args(0.5, 1.15, macos(3.0)),
+            ),
+            (
+                "Classic__SENS_MULT-1-ACCEL-0.3-POWER-2-OFFSET0-OUTPUT_CAP-0.snapshot",
+                args(1.0, 1.0, classic(0.3, 2.0, 0.0, 0.0)),
+            ),
+            (
+                "Classic__SENS_MULT-0.5-ACCEL-0.05-POWER-3-OFFSET2-OUTPUT_CAP-2.5.snapshot",
+                args(0.5, 1.15, classic(0.05, 3.0, 2.0, 2.5)),
+            ),
+        ];
+
+        // In the same order as in `accel.test.c`, for the carried remainders.
+        let mut carry = (Fp::ZERO, Fp::ZERO);
+        for (name, args) in cases {
+            assert_acceleration_snapshot(name, args, &mut carry);
+        }
+    }
+
+    /// The snapshots only cover some parameters, so when the crate is built with the driver's
+    /// code, the port is also checked against it, for every mode and many more speeds.
+    #[cfg(not(feature = "pure_rust"))]
+    #[test]
+    fn curves_match_the_drivers_code() {
+        use crate::{ALL_MODES, LookupTable, Param};
+
+        fn port_sensitivity(speed_in: Fpt, args: &AccelParams) -> (Fpt, Fpt) {
+            fn compute<F: Fixed>(speed_in: Fpt, args: &AccelParams) -> (Fpt, Fpt) {
+                let (x, y) = sensitivity::<F>(fp(speed_in), args);
+                (fpt(x), fpt(y))
+            }
+
+            match FixedptWidth::CURRENT {
+                FixedptWidth::W32 => compute::<Fp32>(speed_in, args),
+                FixedptWidth::W64 => compute::<Fp64>(speed_in, args),
+            }
+        }
+
+        let overrides = [
+            (Param::SensMult, 0.5),
+            (Param::YxRatio, 1.15),
+            (Param::AccelLinear, 0.3),
+            (Param::OffsetLinear, 2.0),
+            (Param::OutputCapLinear, 2.0),
+            (Param::AccelClassic, 0.05),
+            (Param::PowerClassic, 3.0),
+            (Param::OffsetClassic, 2.0),
+            (Param::OutputCapClassic, 2.5),
+        ];
+        let mut params = AllParamArgs::from_fn(|param| {
+            let value = overrides.iter().find(|(p, _)| *p == param);
+            Fpt::from(value.map_or(param.info().default, |&(_, value)| value))
+        });
+        params.lookup_table.points =
+            LookupTable::new(&[(2.0, 1.0), (8.0, 1.5), (32.0, 2.5)]).unwrap();
+
+        for &mode in ALL_MODES {
+            let args = params.convert_to_accel_args(mode);
+            for speed in (0..2000).map(|i| Fpt::from(f64::from(i) * 0.0625)) {
+                let driver = unsafe { super::super::sensitivity_rs(speed, &args) };
+                assert_eq!(
+                    port_sensitivity(speed, &args),
+                    (Fpt(driver.x), Fpt(driver.y)),
+                    "{mode:?} at {speed:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn strings_and_speeds_match_the_drivers_snapshots() {
+        for (value, name) in [
+            (0.25, "0.25"),
+            (0.125, "0.125"),
+            (0.3125, "0.3125"),
+            (-785.0, "-785"),
+        ] {
+            assert_eq!(
+                Fp::rconst(value).fptoa(),
+                snapshot(&format!("fp_to_str.test.c_{name}.snapshot"))
+            );
+        }
+
+        // `atofp.test.c`
+        for (string, value) in [
+            ("1073741824", 0.25),
+            ("536870912", 0.125),
+            ("1342177280", 0.3125),
+            ("-335007449088", -78.0),
+        ] {
+            assert_eq!(atofp::<Fp>(string.as_bytes()).todouble(), value);
+        }
+
+        for (x, y, t) in [
+            (1, 1, 1),
+            (1, 21, 1),
+            (64, -37, 1),
+            (1, 4, 1),
+            (-1, 1, 4),
+            (1, 0, 100),
+            (1, -1, 100),
+            (-1, -24, 1),
+        ] {
+            let speed = input_speed(
+                Fp::rconst(x as f64),
+                Fp::rconst(y as f64),
+                Fp::rconst(t as f64),
+            );
+            assert_eq!(
+                format!(
+                    "(sqrt({:.6}, {:.6}) / {:.6}) = {:.6}\n",
+                    x as f64,
+                    y as f64,
+                    t as f64,
+                    speed.todouble()
+                ),
+                snapshot(&format!("input_speed.test.c_sqrt_{x}_{y}_{t}.snapshot"))
+            );
+        }
+    }
+}
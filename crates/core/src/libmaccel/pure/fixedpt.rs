@@ -0,0 +1,408 @@
+//! A port of `driver/fixedptc.h`, for both widths the driver can be built with.
+//!
+//! The numbers are [`Wrapping`] so that they overflow the way the C code does,
+//! rather than panic, and the functions follow the C line by line,
+//! down to where it truncates, so that they compute the same bits.
+
+// The constants are written exactly as they are in the C code.
+#![allow(clippy::excessive_precision, clippy::approx_constant)]
+
+use std::{
+    num::Wrapping,
+    ops::{Add, AddAssign, BitAnd, BitAndAssign, Neg, Not, Shl, Shr, ShrAssign, Sub, SubAssign},
+};
+
+/// A fixed-point number of one of the widths, `fpt` in the C code.
+pub trait Fixed:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Neg<Output = Self>
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+    + ShrAssign<usize>
+    + BitAnd<Output = Self>
+    + BitAndAssign
+    + Not<Output = Self>
+{
+    /// `FIXEDPT_BITS`
+    const WIDTH: u32;
+    /// `FIXEDPT_FBITS`
+    const FBITS: usize = (Self::WIDTH / 2) as usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The number from its raw bits, truncated to the width like a C conversion.
+    fn from_raw(raw: i64) -> Self;
+    fn to_raw(self) -> i64;
+    /// `fpt_rconst`
+    fn rconst(r: f64) -> Self;
+    /// `fpt_todouble`
+    fn todouble(self) -> f64;
+    /// `fpt_mul`
+    fn fpt_mul(a: Self, b: Self) -> Self;
+    /// `fpt_div`, that the 64-bit version does with a 128-bit by 64-bit division.
+    fn fpt_div(a: Self, b: Self) -> Self;
+}
+
+macro_rules! impl_fixed {
+    ($raw:ty, $wide:ty, $bits:literal) => {
+        impl Fixed for Wrapping<$raw> {
+            const WIDTH: u32 = $bits;
+            const ZERO: Self = Wrapping(0);
+            const ONE: Self = Wrapping(1 << Self::FBITS);
+
+            fn from_raw(raw: i64) -> Self {
+                Wrapping(raw as $raw)
+            }
+
+            fn to_raw(self) -> i64 {
+                i64::from(self.0)
+            }
+
+            fn rconst(r: f64) -> Self {
+                Wrapping((r * Self::ONE.0 as f64 + if r >= 0.0 { 0.5 } else { -0.5 }) as $raw)
+            }
+
+            fn todouble(self) -> f64 {
+                self.0 as f64 * (1.0 / (1i64 << Self::FBITS) as f64)
+            }
+
+            fn fpt_mul(a: Self, b: Self) -> Self {
+                Wrapping(((a.0 as $wide * b.0 as $wide) >> Self::FBITS) as $raw)
+            }
+
+            fn fpt_div(a: Self, b: Self) -> Self {
+                Wrapping((((a.0 as $wide) << Self::FBITS) / b.0 as $wide) as $raw)
+            }
+        }
+    };
+}
+
+pub type Fp32 = Wrapping<i32>;
+pub type Fp64 = Wrapping<i64>;
+
+impl_fixed!(i32, i64, 32);
+impl_fixed!(i64, i128, 64);
+
+fn fmask<F: Fixed>() -> F {
+    F::from_raw((1 << F::FBITS) - 1)
+}
+
+fn one_half<F: Fixed>() -> F {
+    F::ONE >> 1
+}
+
+pub fn two<F: Fixed>() -> F {
+    F::ONE + F::ONE
+}
+
+fn ln2<F: Fixed>() -> F {
+    F::rconst(0.69314718055994530942)
+}
+
+/// `fpt_fromint`
+#[cfg(test)]
+pub fn fromint<F: Fixed>(i: i32) -> F {
+    F::from_raw(i64::from(i) << F::FBITS)
+}
+
+/// `fpt_toint`
+pub fn toint<F: Fixed>(f: F) -> i32 {
+    (f.to_raw() >> F::FBITS) as i32
+}
+
+/// `fpt_mul`
+pub fn mul<F: Fixed>(a: F, b: F) -> F {
+    F::fpt_mul(a, b)
+}
+
+/// `fpt_div`
+pub fn div<F: Fixed>(a: F, b: F) -> F {
+    F::fpt_div(a, b)
+}
+
+/// `fpt_abs`
+pub fn abs<F: Fixed>(a: F) -> F {
+    if a < F::ZERO { -a } else { a }
+}
+
+/// `fpt_sqrt`, or -1 for a negative number.
+#[cfg(test)]
+pub fn sqrt<F: Fixed>(mut a: F) -> F {
+    let mut invert = false;
+    let mut iter = F::FBITS;
+
+    if a < F::ZERO {
+        return F::from_raw(-1);
+    }
+    if a == F::ZERO || a == F::ONE {
+        return a;
+    }
+    if a < F::ONE && a > F::from_raw(6) {
+        invert = true;
+        a = div(F::ONE, a);
+    }
+    if a > F::ONE {
+        let mut s = a;
+
+        iter = 0;
+        while s > F::ZERO {
+            s >>= 2;
+            iter += 1;
+        }
+    }
+
+    // Newton's iterations
+    let mut l = (a >> 1) + F::from_raw(1);
+    for _ in 0..iter {
+        l = (l + div(a, l)) >> 1;
+    }
+    if invert { div(F::ONE, l) } else { l }
+}
+
+/// `fpt_exp`
+pub fn exp<F: Fixed>(mut fp: F) -> F {
+    let ln2_inv = F::rconst(1.4426950408889634074);
+    let exp_p = [
+        F::rconst(1.66666666666666019037e-01),
+        F::rconst(-2.77777777770155933842e-03),
+        F::rconst(6.61375632143793436117e-05),
+        F::rconst(-1.65339022054652515390e-06),
+        F::rconst(4.13813679705723846039e-08),
+    ];
+
+    if fp == F::ZERO {
+        return F::ONE;
+    }
+    let xabs = abs(fp);
+    let mut k = mul(xabs, ln2_inv);
+    k += one_half();
+    k &= !fmask::<F>();
+    if fp < F::ZERO {
+        k = -k;
+    }
+    fp -= mul(k, ln2());
+    let z = mul(fp, fp);
+    // Taylor
+    let r = two::<F>()
+        + mul(
+            z,
+            exp_p[0]
+                + mul(
+                    z,
+                    exp_p[1] + mul(z, exp_p[2] + mul(z, exp_p[3] + mul(z, exp_p[4]))),
+                ),
+        );
+    let xp = F::ONE + div(mul(fp, two()), r - fp);
+    // Like on x86, the shifts only use as many of the low bits as it takes to count the width.
+    k = if k < F::ZERO {
+        F::ONE >> ((-k) >> F::FBITS).to_raw() as usize
+    } else {
+        F::ONE << (k >> F::FBITS).to_raw() as usize
+    };
+    mul(k, xp)
+}
+
+/// `fpt_tanh`
+pub fn tanh<F: Fixed>(x: F) -> F {
+    // `SATURATION`, with `FIXEDPT_WBITS` being half the width
+    let wbits = F::WIDTH / 2;
+    let saturation = F::rconst(((wbits - 1) as f64 * 0.69314718055994530942 - 1.0) / 2.0);
+    if x > saturation {
+        return F::ONE;
+    }
+    if x < -saturation {
+        return -F::ONE;
+    }
+
+    let e_to_the_2_x = exp(mul(two(), x));
+    let sinh = e_to_the_2_x - F::ONE;
+    let cosh = e_to_the_2_x + F::ONE;
+    div(sinh, cosh)
+}
+
+/// `fpt_ln`, or 0 for a negative number.
+pub fn ln<F: Fixed>(x: F) -> F {
+    let lg = [
+        F::rconst(6.666666666666735130e-01),
+        F::rconst(3.999999999940941908e-01),
+        F::rconst(2.857142874366239149e-01),
+        F::rconst(2.222219843214978396e-01),
+        F::rconst(1.818357216161805012e-01),
+        F::rconst(1.531383769920937332e-01),
+        F::rconst(1.479819860511658591e-01),
+    ];
+
+    if x < F::ZERO {
+        return F::ZERO;
+    }
+    if x == F::ZERO {
+        // The C code returns the `0xffffffff` literal, converted to the width.
+        return F::from_raw(0xffff_ffff);
+    }
+
+    let mut log2 = F::ZERO;
+    let mut xi = x;
+    while xi > two() {
+        xi >>= 1;
+        log2 += F::from_raw(1);
+    }
+    while xi < F::ONE {
+        xi = xi << 1;
+        log2 -= F::from_raw(1);
+    }
+    let f = xi - F::ONE;
+    let s = div(f, two::<F>() + f);
+    let z = mul(s, s);
+    let w = mul(z, z);
+    let r = mul(w, lg[1] + mul(w, lg[3] + mul(w, lg[5])))
+        + mul(z, lg[0] + mul(w, lg[2] + mul(w, lg[4] + mul(w, lg[6]))));
+    // `log2 * FIXEDPT_ONE`, which a wrapping shift is the same as, negative or not.
+    mul(ln2(), log2 << F::FBITS) + f - mul(s, f - r)
+}
+
+/// `fpt_pow`, or 0 for a negative `n`.
+pub fn pow<F: Fixed>(n: F, exp: F) -> F {
+    if exp == F::ZERO {
+        return F::ONE;
+    }
+    if n < F::ZERO {
+        return F::ZERO;
+    }
+    self::exp(mul(ln(n), exp))
+}
+
+/// The conversions to and from strings.
+pub mod strings {
+    use super::{Fixed, Fp32, Fp64, fmask};
+
+    /// `fptoa`
+    pub trait Fptoa {
+        fn fptoa(self) -> String;
+    }
+
+    impl Fptoa for Fp64 {
+        fn fptoa(self) -> String {
+            fptoa64(self)
+        }
+    }
+
+    impl Fptoa for Fp32 {
+        fn fptoa(self) -> String {
+            fptoa32(self)
+        }
+    }
+
+    /// `atofp`, which takes the digits as those of the raw number, ignoring anything else.
+    ///
+    /// It's computed on 64 bits, then truncated, which gives the same bits as
+    /// computing it on the width, wrapping around the same way.
+    #[cfg(any(feature = "pure_rust", test))]
+    pub fn atofp<F: Fixed>(num_string: &[u8]) -> F {
+        let mut n: u64 = 0;
+        let mut sign = false;
+
+        for &c in num_string {
+            match c {
+                b' ' | b'\n' => continue,
+                b'-' => sign = true,
+                b'0'..=b'9' => n = n.wrapping_mul(10).wrapping_add(u64::from(c - b'0')),
+                _ => {}
+            }
+        }
+
+        let n = if sign { n.wrapping_neg() } else { n };
+        F::from_raw(n as i64)
+    }
+
+    /// `fptoa`, which is `FP64_ToString` from `Fixed64.utils.h` for the 64-bit width.
+    fn fptoa64(value: Fp64) -> String {
+        const SCALE: u64 = 1_000_000_000;
+
+        let mut buf = String::new();
+        let uvalue = value.0.unsigned_abs();
+        if value < Fp64::ZERO {
+            buf.push('-');
+        }
+
+        // Separate the integer and decimal parts of the value
+        let mut intpart = uvalue >> 32;
+        let mut fracpart = uvalue & 0xFFFF_FFFF;
+        fracpart = ((fracpart as i128 * SCALE as i128) >> 32) as u64;
+
+        if fracpart >= SCALE {
+            // Handle carry from decimal part
+            intpart += 1;
+            fracpart -= SCALE;
+        }
+
+        itoa_loop(&mut buf, 1_000_000_000, intpart, true);
+        buf.push('.');
+        itoa_loop(&mut buf, SCALE / 10, fracpart, false);
+
+        buf
+    }
+
+    fn itoa_loop(buf: &mut String, mut scale: u64, mut value: u64, mut skip: bool) {
+        while scale != 0 {
+            let digit = (value / scale) as u32;
+
+            if !skip || digit != 0 || scale == 1 {
+                skip = false;
+                buf.push(char::from(b'0'.wrapping_add(digit as u8)));
+                value %= scale;
+            }
+
+            scale /= 10;
+        }
+    }
+
+    /// `fptoa`, which is `fpt_str` with 10 decimals for the 32-bit width.
+    fn fptoa32(mut a: Fp32) -> String {
+        const MAX_DEC: usize = 10;
+        const ONE_UD: u64 = 1 << Fp32::WIDTH;
+        const MASK: u64 = ONE_UD - 1;
+
+        let mut buf = String::new();
+        if a < Fp32::ZERO {
+            buf.push('-');
+            a = -a;
+        }
+
+        let mut ip = (a.0 >> Fp32::FBITS) as u64;
+        let mut digits = vec![];
+        loop {
+            digits.push(char::from(b'0' + (ip % 10) as u8));
+            ip /= 10;
+            if ip == 0 {
+                break;
+            }
+        }
+        buf.extend(digits.iter().rev());
+        buf.push('.');
+
+        let mut ndec = 0;
+        let mut fr =
+            ((a & fmask::<Fp32>()) << (Fp32::WIDTH - Fp32::FBITS as u32) as usize).0 as u64 & MASK;
+        loop {
+            fr = (fr & MASK) * 10;
+
+            buf.push(char::from(b'0' + ((fr >> Fp32::WIDTH) % 10) as u8));
+            ndec += 1;
+            if fr == 0 || ndec >= MAX_DEC {
+                break;
+            }
+        }
+
+        // cut off trailing 0
+        if ndec > 1 && buf.ends_with('0') {
+            buf.pop();
+        }
+
+        buf
+    }
+}
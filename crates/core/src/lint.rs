@@ -0,0 +1,360 @@
+//! Checks on how a curve feels, for parameters that are valid, and make a working curve,
+//! but likely not the one that was intended: flat curves, parameters that don't change anything,
+//! or a cursor that slows down as the mouse speeds up.
+//!
+//! Unlike [`validate_params`](crate::validation::validate_params), these look at the curve
+//! over the input speeds the TUI graphs, as the driver computes it.
+
+use crate::{
+    AccelMode, AllParamArgs, Param, gain, reference, sensitivity,
+    validation::{Issue, Severity},
+};
+
+/// The fastest input speed, in counts/ms, we look at the curve up to, as in the TUI graph.
+pub const MAX_SPEED: f64 = 128.0;
+
+/// How far apart, in counts/ms, the input speeds we look at the curve at are.
+const STEP: f64 = MAX_SPEED / 1024.0;
+
+/// How much the sensitivity can change right past the offset before it's a jump,
+/// relative to the sens multiplier.
+const JUMP_THRESHOLD: f64 = 0.05;
+
+/// How negative the gain can be, and still be the rounding of a flat output speed.
+const GAIN_TOLERANCE: f64 = 0.01;
+
+/// Find what's likely wrong with how `mode`'s curve feels, with `args`.
+pub fn lint(mode: AccelMode, args: &AllParamArgs) -> Vec<Issue> {
+    let mut lints = Lints::default();
+    let value = |param: Param| f64::from(args.get(param));
+
+    match mode {
+        AccelMode::Linear => {
+            if value(Param::AccelLinear) == 0.0 {
+                lints.check_flat(
+                    &[Param::AccelLinear],
+                    mode,
+                    args,
+                    "an acceleration of 0 makes the curve flat",
+                );
+            }
+            lints.check_output_cap(
+                &[Param::OutputCapLinear, Param::AccelLinear],
+                value(Param::OutputCapLinear),
+                |s_in| {
+                    reference::linear(
+                        s_in,
+                        value(Param::AccelLinear),
+                        value(Param::OffsetLinear),
+                        0.0,
+                    ) - 1.0
+                },
+            );
+            lints.check_offset(&[Param::OffsetLinear], mode, args);
+        }
+        AccelMode::Classic => {
+            if value(Param::AccelClassic) == 0.0 {
+                lints.check_flat(
+                    &[Param::AccelClassic],
+                    mode,
+                    args,
+                    "an acceleration of 0 makes the curve flat",
+                );
+            }
+            lints.check_output_cap(
+                &[
+                    Param::OutputCapClassic,
+                    Param::AccelClassic,
+                    Param::PowerClassic,
+                ],
+                value(Param::OutputCapClassic),
+                |s_in| {
+                    reference::classic(
+                        s_in,
+                        value(Param::AccelClassic),
+                        value(Param::PowerClassic),
+                        value(Param::OffsetClassic),
+                        0.0,
+                    ) - 1.0
+                },
+            );
+            lints.check_offset(&[Param::OffsetClassic], mode, args);
+        }
+        AccelMode::Natural => {
+            // `__natural_sens_fun` just returns 1 for these.
+            if value(Param::Limit) <= 1.0 {
+                lints.warn(&[Param::Limit], "a limit of 1 or less makes the curve flat");
+            } else if value(Param::DecayRate) <= 0.0 {
+                lints.warn(
+                    &[Param::DecayRate],
+                    "a decay rate that's not positive makes the curve flat",
+                );
+            }
+            lints.check_offset(&[Param::OffsetNatural], mode, args);
+        }
+        AccelMode::Synchronous | AccelMode::Windows => {}
+        AccelMode::Jump => {
+            if value(Param::JumpSens) == 1.0 {
+                lints.warn(
+                    &[Param::JumpSens],
+                    "a jump sensitivity of 1 makes the curve flat",
+                );
+            } else if value(Param::JumpSpeed) >= MAX_SPEED {
+                lints.warn(
+                    &[Param::JumpSpeed],
+                    format!(
+                        "the jump speed is past {MAX_SPEED} counts/ms, so the curve never jumps"
+                    ),
+                );
+            }
+        }
+        AccelMode::Power => {
+            let output_cap = value(Param::OutputCapPower);
+            let output_offset = value(Param::OutputOffsetPower);
+            if output_cap != 0.0 && output_cap <= output_offset {
+                lints.warn(
+                    &[Param::OutputCapPower, Param::OutputOffsetPower],
+                    "an output cap at or below the output offset makes the curve flat",
+                );
+            } else if output_cap != 0.0 {
+                let reached = speeds().any(|s_in| {
+                    reference::power(
+                        s_in,
+                        value(Param::ScalePower),
+                        value(Param::ExponentPower),
+                        output_offset,
+                        0.0,
+                    ) >= output_cap
+                });
+                if !reached {
+                    lints.warn(
+                        &[
+                            Param::OutputCapPower,
+                            Param::ScalePower,
+                            Param::ExponentPower,
+                        ],
+                        format!("the output cap is never reached below {MAX_SPEED} counts/ms"),
+                    );
+                }
+            }
+        }
+        AccelMode::Macos => {
+            if value(Param::TrackingSpeed) == 0.0 {
+                lints.warn(
+                    &[Param::TrackingSpeed],
+                    "a tracking speed of 0 makes the curve flat",
+                );
+            }
+        }
+        AccelMode::LookupTable => {
+            let points = args.lookup_table.points.points();
+            if points.windows(2).all(|p| p[0].1 == p[1].1) {
+                lints.warn(
+                    &[],
+                    "a look-up table with no change in sensitivity makes the curve flat",
+                );
+            }
+        }
+    }
+
+    lints.check_gain(mode, args);
+
+    lints.issues
+}
+
+#[derive(Default)]
+struct Lints {
+    issues: Vec<Issue>,
+}
+
+impl Lints {
+    fn warn(&mut self, params: &'static [Param], message: impl Into<String>) {
+        self.issues.push(Issue {
+            severity: Severity::Warning,
+            params,
+            message: message.into(),
+        });
+    }
+
+    /// Warn with `message` if the driver's sensitivity is the same at every speed,
+    /// which isn't always the case for parameters that make the formula flat.
+    /// E.g. the driver's Classic curve, with an acceleration of 0, takes `fpt_ln(0)`.
+    fn check_flat(
+        &mut self,
+        params: &'static [Param],
+        mode: AccelMode,
+        args: &AllParamArgs,
+        message: &str,
+    ) {
+        let mut sens = speeds().map(|s_in| sensitivity(s_in, mode, args).0);
+        let first = sens.next();
+        if sens.all(|s| Some(s) == first) {
+            self.warn(params, message);
+        }
+    }
+
+    /// The output cap limits how far the sensitivity gets from 1, which `acceleration`
+    /// computes for a speed, without the cap.
+    fn check_output_cap(
+        &mut self,
+        params: &'static [Param],
+        output_cap: f64,
+        acceleration: impl Fn(f64) -> f64,
+    ) {
+        if output_cap == 0.0 {
+            return;
+        }
+        if output_cap == 1.0 {
+            self.warn(
+                params,
+                "an output cap of 1 makes the curve flat, use 0 for no cap",
+            );
+            return;
+        }
+
+        let reached = speeds().any(|s_in| acceleration(s_in) >= (output_cap - 1.0).abs());
+        if !reached {
+            self.warn(
+                params,
+                format!("the output cap is never reached below {MAX_SPEED} counts/ms"),
+            );
+        }
+    }
+
+    /// Up to the offset the sensitivity is flat, and from there, it should start
+    /// to change smoothly.
+    fn check_offset(&mut self, params: &'static [Param], mode: AccelMode, args: &AllParamArgs) {
+        let offset = f64::from(args.get(params[0]));
+        if offset >= MAX_SPEED {
+            self.warn(
+                params,
+                format!("the offset is past {MAX_SPEED} counts/ms, so the curve never accelerates"),
+            );
+            return;
+        }
+
+        let (at_offset, _) = sensitivity(offset, mode, args);
+        let (past_offset, _) = sensitivity(offset + STEP, mode, args);
+        let jump = past_offset - at_offset;
+        let sens_mult = f64::from(args.sens_mult).abs();
+
+        if jump.abs() > JUMP_THRESHOLD * sens_mult {
+            self.warn(
+                params,
+                format!("the sensitivity jumps by {jump:.2} right past the offset"),
+            );
+        }
+    }
+
+    /// A negative gain means the cursor slows down while the mouse speeds up.
+    fn check_gain(&mut self, mode: AccelMode, args: &AllParamArgs) {
+        let mut negative_from = None;
+
+        for s_in in speeds().chain([f64::INFINITY]) {
+            let is_negative = s_in.is_finite() && gain(s_in, mode, args).0 < -GAIN_TOLERANCE;
+
+            match (negative_from, is_negative) {
+                (None, true) => negative_from = Some(s_in),
+                (Some(from), false) => {
+                    let to = s_in.min(MAX_SPEED);
+                    self.warn(
+                        mode.params(),
+                        format!("the gain is negative between {from:.2} and {to:.2} counts/ms"),
+                    );
+                    negative_from = None;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The input speeds we look at the curve at.
+fn speeds() -> impl Iterator<Item = f64> {
+    (1..=(MAX_SPEED / STEP) as usize).map(|i| i as f64 * STEP)
+}
+
+#[cfg(test)]
+#[test]
+fn lint_finds_curves_that_feel_wrong() {
+    use crate::fixedptc::Fpt;
+
+    let args = |overrides: &[(Param, f64)]| {
+        AllParamArgs::from_fn(|p| {
+            let value = overrides
+                .iter()
+                .find(|(o, _)| *o == p)
+                .map_or(1.0, |&(_, v)| v);
+            Fpt::from(value)
+        })
+    };
+    let messages = |mode, args: &AllParamArgs| {
+        lint(mode, args)
+            .into_iter()
+            .map(|issue| issue.message)
+            .collect::<Vec<_>>()
+    };
+
+    let fine = args(&[
+        (Param::AccelLinear, 0.3),
+        (Param::OffsetLinear, 2.0),
+        (Param::OutputCapLinear, 2.0),
+    ]);
+    assert_eq!(messages(AccelMode::Linear, &fine), Vec::<String>::new());
+
+    let never_capped = args(&[
+        (Param::AccelLinear, 0.001),
+        (Param::OffsetLinear, 0.0),
+        (Param::OutputCapLinear, 3.0),
+    ]);
+    assert_eq!(
+        messages(AccelMode::Linear, &never_capped),
+        ["the output cap is never reached below 128 counts/ms"]
+    );
+
+    let flat = args(&[(Param::Limit, 1.0)]);
+    assert_eq!(
+        messages(AccelMode::Natural, &flat),
+        ["a limit of 1 or less makes the curve flat"]
+    );
+
+    // The driver's default Classic curve isn't flat, `fpt_pow(0, 2)` takes `fpt_ln(0)`.
+    let default_classic = AllParamArgs::from_fn(|p| Fpt::from(p.info().default));
+    let lints = messages(AccelMode::Classic, &default_classic);
+    assert!(
+        !lints.iter().any(|l| l.ends_with("makes the curve flat")),
+        "{lints:?}"
+    );
+    let flat_linear = args(&[(Param::AccelLinear, 0.0), (Param::OutputCapLinear, 0.0)]);
+    assert_eq!(
+        messages(AccelMode::Linear, &flat_linear),
+        ["an acceleration of 0 makes the curve flat"]
+    );
+
+    // The power below 1 makes it shoot up from the offset.
+    let jumpy = args(&[
+        (Param::AccelClassic, 0.3),
+        (Param::PowerClassic, 0.5),
+        (Param::OffsetClassic, 2.0),
+        (Param::OutputCapClassic, 0.0),
+    ]);
+    let lints = messages(AccelMode::Classic, &jumpy);
+    assert_eq!(lints.len(), 1, "{lints:?}");
+    assert!(
+        lints[0].starts_with("the sensitivity jumps by"),
+        "{lints:?}"
+    );
+
+    // The output speed is `s - (as)^2` until the sensitivity gets capped at `1 - 0.8`,
+    // so the gain `1 - 2a^2s` is negative from `s = 1/(2a^2)`, until `s = 0.8/a^2`.
+    let slowing = args(&[
+        (Param::AccelClassic, 0.5),
+        (Param::PowerClassic, 2.0),
+        (Param::OffsetClassic, 0.0),
+        (Param::OutputCapClassic, 0.2),
+    ]);
+    assert_eq!(
+        messages(AccelMode::Classic, &slowing),
+        ["the gain is negative between 2.12 and 3.25 counts/ms"]
+    );
+}
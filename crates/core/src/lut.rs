@@ -0,0 +1,283 @@
+//! The [`AccelMode::LookupTable`] curve, which is made of points rather than parameters:
+//! the sensitivity at some input speeds, interpolated linearly in between,
+//! see `driver/accel/lut.h`.
+//!
+//! [`AccelMode::LookupTable`]: crate::AccelMode::LookupTable
+
+use std::{ffi::c_ulong, fmt::Debug, fs, path::Path};
+
+use crate::{
+    Error,
+    fixedptc::{FIXEDPT_BITS, Fpt},
+    persist::Transaction,
+};
+
+/// How many points the driver takes, `LUT_MAX_POINTS` in `driver/accel/lut.h`,
+/// or as it's set when building, for this crate and the driver alike, see `build.rs`.
+pub const LUT_MAX_POINTS: usize = match usize::from_str_radix(env!("LUT_MAX_POINTS"), 10) {
+    Ok(n) => n,
+    Err(_) => panic!("LUT_MAX_POINTS should be a number"),
+};
+
+/// The points of the look-up table, as `(input speed, sensitivity)`, by increasing input speed.
+/// Before the first point and after the last, the sensitivity is theirs,
+/// and without points, it's 1.
+#[derive(Default, Clone, PartialEq)]
+pub struct LookupTable {
+    points: Vec<(f64, f64)>,
+}
+
+impl LookupTable {
+    /// The name of the parameter the driver takes the points in.
+    pub const PARAM_NAME: &'static str = "LUT_DATA";
+
+    /// Check that the driver can take the points: there aren't too many,
+    /// the speeds increase from one point to the next, even as fixed-point numbers,
+    /// and nothing is negative.
+    pub fn new(points: &[(f64, f64)]) -> crate::Result<Self> {
+        let invalid = |reason: String| Err(Error::InvalidLookupTable(reason));
+
+        if points.len() > LUT_MAX_POINTS {
+            return invalid(format!(
+                "there are {} points, the driver takes at most {LUT_MAX_POINTS}",
+                points.len()
+            ));
+        }
+
+        let mut previous_speed = None;
+        for &(speed, sens) in points {
+            for value in [speed, sens] {
+                if Fpt::checked_from(value).is_none() {
+                    return invalid(format!(
+                        "{value} cannot be represented as a {FIXEDPT_BITS}-bit fixed-point number"
+                    ));
+                }
+            }
+            if speed < 0.0 || sens < 0.0 {
+                return invalid(format!("({speed}, {sens}) is negative"));
+            }
+
+            let speed = Fpt::from(speed);
+            if previous_speed.is_some_and(|previous: Fpt| speed.0 <= previous.0) {
+                return invalid(format!(
+                    "the input speeds must increase from one point to the next, {} doesn't",
+                    f64::from(speed)
+                ));
+            }
+            previous_speed = Some(speed);
+        }
+
+        Ok(Self {
+            points: points.to_vec(),
+        })
+    }
+
+    /// Read the points from a text file, see [`LookupTable::parse`].
+    pub fn from_file(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+
+        Self::parse(&content).map_err(|err| match err {
+            Error::InvalidLookupTable(reason) => Error::parse(path, reason),
+            err => err,
+        })
+    }
+
+    /// Read the points from text, one per line, with the input speed, in counts/ms,
+    /// and the sensitivity separated by a comma or spaces, e.g.
+    ///
+    /// ```text
+    /// # speed, sensitivity
+    /// 0, 1
+    /// 8, 1.5
+    /// 32, 2.5
+    /// ```
+    ///
+    /// Blank lines, and anything after a `#`, are skipped.
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        let mut points = vec![];
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let numbers: Vec<_> = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|n| !n.is_empty())
+                .map(str::parse::<f64>)
+                .collect();
+            match numbers[..] {
+                [Ok(speed), Ok(sens)] => points.push((speed, sens)),
+                _ => {
+                    return Err(Error::InvalidLookupTable(format!(
+                        "line {}: expected an input speed and a sensitivity, got {line:?}",
+                        idx + 1
+                    )));
+                }
+            }
+        }
+
+        Self::new(&points)
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The points as the driver takes them in [`LookupTable::PARAM_NAME`]:
+    /// raw fixed-point numbers separated by commas, each speed followed by its sensitivity.
+    pub fn to_driver_string(&self) -> String {
+        let numbers: Vec<_> = self
+            .points()
+            .iter()
+            .flat_map(|&(speed, sens)| [Fpt::from(speed).0, Fpt::from(sens).0])
+            .map(|raw| raw.to_string())
+            .collect();
+
+        numbers.join(",")
+    }
+
+    /// The points from what the driver gives for [`LookupTable::PARAM_NAME`],
+    /// see [`LookupTable::to_driver_string`].
+    pub fn from_driver_string(string: &str) -> crate::Result<Self> {
+        let invalid = || Error::InvalidLookupTable(format!("{string:?} is not a list of points"));
+
+        let numbers = string
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|n| !n.is_empty())
+            .map(|n| n.parse().map(|raw| f64::from(Fpt(raw))))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        if numbers.len() % 2 != 0 {
+            return Err(invalid());
+        }
+
+        let points: Vec<_> = numbers.chunks(2).map(|p| (p[0], p[1])).collect();
+        Self::new(&points)
+    }
+}
+
+impl Debug for LookupTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.points()).finish()
+    }
+}
+
+/// Serialized as a list of `[speed, sensitivity]` pairs.
+#[cfg(feature = "serde")]
+impl serde::Serialize for LookupTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.points().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for LookupTable {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let points = Vec::<(f64, f64)>::deserialize(deserializer)?;
+        Self::new(&points).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The look-up table as the driver takes it, `struct lut_curve_args` in `driver/accel/lut.h`.
+#[repr(C)]
+pub struct LookupTableCurveParams {
+    pub len: c_ulong,
+    pub speeds: [Fpt; LUT_MAX_POINTS],
+    pub sens: [Fpt; LUT_MAX_POINTS],
+}
+
+impl From<&LookupTable> for LookupTableCurveParams {
+    fn from(table: &LookupTable) -> Self {
+        let mut params = Self {
+            len: table.points.len() as c_ulong,
+            speeds: [Fpt(0); LUT_MAX_POINTS],
+            sens: [Fpt(0); LUT_MAX_POINTS],
+        };
+        for (idx, &(speed, sens)) in table.points().iter().enumerate() {
+            params.speeds[idx] = speed.into();
+            params.sens[idx] = sens.into();
+        }
+        params
+    }
+}
+
+impl From<&LookupTableCurveParams> for LookupTable {
+    fn from(params: &LookupTableCurveParams) -> Self {
+        let len = (params.len as usize).min(LUT_MAX_POINTS);
+        let points = params.speeds[..len]
+            .iter()
+            .zip(&params.sens[..len])
+            .map(|(&speed, &sens)| (speed.into(), sens.into()))
+            .collect();
+        Self { points }
+    }
+}
+
+/// The points of the look-up table curve.
+/// Use it to set them all at once.
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LookupTableParamArgs {
+    /// A file with a point per line: an input speed, in counts/ms, and its sensitivity,
+    /// separated by a comma or spaces
+    #[cfg_attr(
+        feature = "clap",
+        arg(value_name = "FILE", value_parser = |path: &str| LookupTable::from_file(path))
+    )]
+    pub points: LookupTable,
+}
+
+impl From<LookupTableParamArgs> for Transaction {
+    fn from(args: LookupTableParamArgs) -> Self {
+        Self::new().lookup_table(args.points)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn lookup_tables_are_checked_and_match_the_driver() {
+    let table = LookupTable::parse("# speed, sensitivity\n0, 1\n\n8 1.5 # fast\n32,2.5\n").unwrap();
+    assert_eq!(table.points(), [(0.0, 1.0), (8.0, 1.5), (32.0, 2.5)]);
+    assert_eq!(
+        table.to_driver_string(),
+        [0.0, 1.0, 8.0, 1.5, 32.0, 2.5]
+            .map(|v| Fpt::from(v).0.to_string())
+            .join(",")
+    );
+    assert_eq!(
+        LookupTable::from_driver_string(&table.to_driver_string()).unwrap(),
+        table
+    );
+    assert_eq!(
+        LookupTable::from_driver_string("\n").unwrap(),
+        LookupTable::default()
+    );
+
+    for invalid in [
+        "0, 1\n0, 2",
+        "8, 1\n2, 1",
+        "0, -1",
+        "0",
+        "0, 1, 2",
+        "zero, 1",
+    ] {
+        assert!(
+            matches!(
+                LookupTable::parse(invalid),
+                Err(Error::InvalidLookupTable(_))
+            ),
+            "{invalid:?}"
+        );
+    }
+    let too_many: Vec<_> = (0..=LUT_MAX_POINTS).map(|i| (i as f64, 1.0)).collect();
+    assert!(LookupTable::new(&too_many).is_err());
+    assert!(LookupTable::new(&too_many[1..]).is_ok());
+}
@@ -2,48 +2,139 @@ use crate::libmaccel::fixedptc;
 use crate::libmaccel::fixedptc::Fpt;
 use paste::paste;
 
-use std::{fmt::Display, str::FromStr};
-
 /// Declare an enum for every parameter.
 macro_rules! declare_common_params {
-    ($($param:tt,)+) => {
+    ($( $(#[doc = $doc:literal])* $param:ident, )+ ; $( $table_mode:ident, )*) => {
         #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(rename_all = "snake_case")
+        )]
         #[derive(Debug, Clone, Copy, PartialEq)]
         pub enum Param {
-            $($param),+
+            $( $(#[doc = $doc])* $param ),+
         }
 
         paste!(
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             #[derive(Debug)]
             pub struct AllParamArgs {
-                $( pub [< $param:snake:lower >]: Fpt ),+
+                $( pub [< $param:snake:lower >]: Fpt, )+
+                $(
+                    #[cfg_attr(feature = "serde", serde(default))]
+                    pub [< $table_mode:snake:lower >]: crate::[< $table_mode ParamArgs >],
+                )*
+            }
+
+            impl AllParamArgs {
+                /// Collect every parameter's value, as given by `value_of`,
+                /// leaving the curves that aren't made of parameters empty.
+                pub fn try_from_fn(
+                    mut value_of: impl FnMut(Param) -> crate::Result<Fpt>,
+                ) -> crate::Result<Self> {
+                    Ok(Self {
+                        $( [< $param:snake:lower >]: value_of(Param::$param)?, )+
+                        $( [< $table_mode:snake:lower >]: Default::default(), )*
+                    })
+                }
+
+                /// Collect every parameter's value, as given by `value_of`,
+                /// leaving the curves that aren't made of parameters empty.
+                pub fn from_fn(mut value_of: impl FnMut(Param) -> Fpt) -> Self {
+                    Self {
+                        $( [< $param:snake:lower >]: value_of(Param::$param), )+
+                        $( [< $table_mode:snake:lower >]: Default::default(), )*
+                    }
+                }
+
+                pub fn get(&self, param: Param) -> Fpt {
+                    match param {
+                        $( Param::$param => self.[< $param:snake:lower >], )+
+                    }
+                }
             }
         );
 
         pub const ALL_PARAMS: &[Param] = &[ $(Param::$param),+ ];
+
+        impl Param {
+            /// A stable identifier for the parameter, independent of the kernel module's naming.
+            /// It's the same as the parameter's field name in [`AllParamArgs`],
+            /// and how it's serialized with the `serde` feature.
+            pub fn key(&self) -> &'static str {
+                paste!(match self {
+                    $( Param::$param => stringify!([< $param:snake:lower >]), )+
+                })
+            }
+        }
     };
 }
 
+/// Declare the parameters, and the modes with their parameters,
+/// followed by the modes whose curve isn't made of parameters, like the look-up table,
+/// which have hand-written `<Mode>CurveParams` and `<Mode>ParamArgs`.
 macro_rules! declare_params {
-    ( Common { $($common_param:tt),+$(,)? } , $( $mode:tt { $($param:tt),+$(,)? }, )+) => {
+    (
+        Common { $( $(#[doc = $common_doc:literal])+ $common_param:ident $common_info:tt ),+$(,)? },
+        $( $mode:ident { $( $(#[doc = $doc:literal])+ $param:ident $info:tt ),+$(,)? } ),+;
+        $( $table_mode:ident ),* $(,)?
+    ) => {
         declare_common_params! {
-            $( $common_param, )+
-            $( $( $param, )+ )+
+            $( $(#[doc = $common_doc])+ $common_param, )+
+            $( $( $(#[doc = $doc])+ $param, )+ )+
+            ;
+            $( $table_mode, )*
         }
 
+        /// What every parameter means, and the values it takes, in the order of [`ALL_PARAMS`].
+        pub const ALL_PARAM_INFO: &[ParamInfo] = &[
+            $( param_info!(
+                $common_param, None, concat!($($common_doc),+), $common_info
+            ), )+
+            $( $( param_info!(
+                $param, Some(AccelMode::$mode), concat!($($doc),+), $info
+            ), )+ )+
+        ];
+
         /// Array of all the common parameters for convenience.
         pub const ALL_COMMON_PARAMS: &[Param] = &[ $( Param::$common_param),+ ];
 
 
         #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(rename_all = "snake_case")
+        )]
         #[derive(Debug, Default, PartialEq, Clone, Copy)]
         #[repr(u8)]
         pub enum AccelMode {
             #[default]
             $( $mode, )+
+            $( $table_mode, )*
         }
 
-        pub const ALL_MODES: &[AccelMode] = &[ $( AccelMode::$mode, )+ ];
+        pub const ALL_MODES: &[AccelMode] = &[ $( AccelMode::$mode, )+ $( AccelMode::$table_mode, )* ];
+
+        impl AccelMode {
+            /// A stable identifier for the mode, the same as the mode's name in the driver,
+            /// and how it's serialized with the `serde` feature.
+            pub fn key(&self) -> &'static str {
+                paste!(match self {
+                    $( AccelMode::$mode => stringify!([< $mode:snake:lower >]), )+
+                    $( AccelMode::$table_mode => stringify!([< $table_mode:snake:lower >]), )*
+                })
+            }
+
+            /// The parameters specific to this mode's curve.
+            pub fn params(&self) -> &'static [Param] {
+                paste!(match self {
+                    $( AccelMode::$mode => [< ALL_ $mode:upper _PARAMS >], )+
+                    $( AccelMode::$table_mode => &[], )*
+                })
+            }
+        }
 
         paste! {
             /// Define the complete shape (and memory layout) of the argument
@@ -60,14 +151,37 @@ macro_rules! declare_params {
                 $(
                     $mode([< $mode CurveParams >] ),
                 )+
+                // Behind a pointer, like in the driver, since it's much bigger than the others.
+                $(
+                    $table_mode(Box<crate::[< $table_mode CurveParams >]>),
+                )*
             }
 
             /// Represents the common parameters and their float values.
             /// Use it to bulk set the common parameters.
             #[cfg_attr(feature = "clap", derive(clap::Args))]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             #[derive(Debug, Clone, Copy, PartialEq)]
             pub struct CommonParamArgs {
-                $( pub [< $common_param:snake:lower >]: f64 ),+
+                $(
+                    #[cfg_attr(feature = "clap", arg(help = Param::$common_param.info().help()))]
+                    pub [< $common_param:snake:lower >]: f64
+                ),+
+            }
+
+            impl From<&AllParamArgs> for CommonParamArgs {
+                fn from(args: &AllParamArgs) -> Self {
+                    Self {
+                        $( [< $common_param:snake:lower >]: args.[< $common_param:snake:lower >].into(), )+
+                    }
+                }
+            }
+
+            impl From<CommonParamArgs> for crate::persist::Transaction {
+                fn from(args: CommonParamArgs) -> Self {
+                    Self::new()
+                        $( .set(Param::$common_param, args.[< $common_param:snake:lower >]) )+
+                }
             }
         }
 
@@ -85,12 +199,112 @@ macro_rules! declare_params {
                 #[doc = "Represents the parameters for `" $mode "` curve and their float values"]
                 /// Use it to bulk set the curve's parameters.
                 #[cfg_attr(feature = "clap", derive(clap::Args))]
+                #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
                 #[derive(Debug, Clone, Copy, PartialEq)]
                 pub struct [< $mode ParamArgs >] {
-                    $( pub [< $param:snake:lower >]: f64 ),+
+                    $(
+                        #[cfg_attr(feature = "clap", arg(help = Param::$param.info().help()))]
+                        pub [< $param:snake:lower >]: f64
+                    ),+
+                }
+
+                impl From<&AllParamArgs> for [< $mode ParamArgs >] {
+                    fn from(args: &AllParamArgs) -> Self {
+                        Self {
+                            $( [< $param:snake:lower >]: args.[< $param:snake:lower >].into(), )+
+                        }
+                    }
+                }
+
+                impl From<[< $mode ParamArgs >]> for crate::persist::Transaction {
+                    fn from(args: [< $mode ParamArgs >]) -> Self {
+                        Self::new()
+                            $( .set(Param::$param, args.[< $param:snake:lower >]) )+
+                    }
                 }
             )+
 
+            /// One mode's curve, with only the values of that mode's parameters,
+            /// see [`Curve`](crate::Curve) for a curve with the common parameters.
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            #[cfg_attr(feature = "serde", serde(tag = "mode", rename_all = "snake_case"))]
+            #[derive(Debug, Clone, PartialEq)]
+            pub enum CurveConfig {
+                $( $mode([< $mode ParamArgs >]), )+
+                $( $table_mode(crate::[< $table_mode ParamArgs >]), )*
+            }
+
+            impl CurveConfig {
+                /// The curve for `mode`, with its parameters' values as given by `value_of`,
+                /// or empty if it isn't made of parameters.
+                pub fn from_fn(mode: AccelMode, mut value_of: impl FnMut(Param) -> f64) -> Self {
+                    match mode {
+                        $(
+                            AccelMode::$mode => Self::$mode([< $mode ParamArgs >] {
+                                $( [< $param:snake:lower >]: value_of(Param::$param), )+
+                            }),
+                        )+
+                        $( AccelMode::$table_mode => Self::$table_mode(Default::default()), )*
+                    }
+                }
+
+                /// The curve for `mode`, as it is in `args`.
+                pub fn from_args(mode: AccelMode, args: &AllParamArgs) -> Self {
+                    match mode {
+                        $(
+                            AccelMode::$table_mode => {
+                                Self::$table_mode(args.[< $table_mode:snake:lower >].clone())
+                            }
+                        )*
+                        _ => Self::from_fn(mode, |param| args.get(param).into()),
+                    }
+                }
+
+                pub fn mode(&self) -> AccelMode {
+                    match self {
+                        $( Self::$mode(_) => AccelMode::$mode, )+
+                        $( Self::$table_mode(_) => AccelMode::$table_mode, )*
+                    }
+                }
+
+                /// The curve's parameters as the driver takes them.
+                pub fn to_params_by_mode(&self) -> AccelParamsByMode {
+                    match self {
+                        $(
+                            Self::$mode(args) => AccelParamsByMode::$mode([< $mode CurveParams >] {
+                                $( [< $param:snake:lower >]: args.[< $param:snake:lower >].into(), )+
+                            }),
+                        )+
+                        $(
+                            Self::$table_mode(args) => {
+                                AccelParamsByMode::$table_mode(Box::new((&args.points).into()))
+                            }
+                        )*
+                    }
+                }
+
+                /// The value of every parameter of the mode, in the order of [`AccelMode::params`].
+                pub fn values(&self) -> Vec<(Param, f64)> {
+                    match self {
+                        $(
+                            Self::$mode(args) => vec![
+                                $( (Param::$param, args.[< $param:snake:lower >]), )+
+                            ],
+                        )+
+                        $( Self::$table_mode(_) => vec![], )*
+                    }
+                }
+            }
+
+            impl From<CurveConfig> for crate::persist::Transaction {
+                fn from(config: CurveConfig) -> Self {
+                    match config {
+                        $( CurveConfig::$mode(args) => args.into(), )+
+                        $( CurveConfig::$table_mode(args) => args.into(), )*
+                    }
+                }
+            }
+
             /// Subcommands for the CLI
             #[cfg(feature = "clap")]
             pub mod subcommads {
@@ -102,12 +316,22 @@ macro_rules! declare_params {
                         #[doc = "Set all the parameters for the " $mode " curve" ]
                         $mode(super::[< $mode ParamArgs >]),
                     )+
+                    $(
+                        #[doc = "Set the points of the " $table_mode " curve" ]
+                        $table_mode(crate::[< $table_mode ParamArgs >]),
+                    )*
                 }
 
                 #[derive(clap::Subcommand)]
                 pub enum CliSubcommandSetParams {
                     /// Set the value for a single parameter
-                    Param { name: crate::params::Param, value: f64 },
+                    Param {
+                        name: crate::params::Param,
+                        /// The value, which can end with a unit, like `1600dpi` or `5in/s`,
+                        /// or be an expression, like `1/30`
+                        #[arg(allow_hyphen_values = true)]
+                        value: String,
+                    },
                     /// Set the acceleration mode (curve)
                     Mode { mode: crate::params::AccelMode },
                     /// Set the values for all parameters for a curve in order
@@ -123,8 +347,12 @@ macro_rules! declare_params {
                     Common,
                     $(
                         #[doc = "Get all the parameters for the " $mode " curve" ]
-                        $mode
-                     ),+
+                        $mode,
+                    )+
+                    $(
+                        #[doc = "Get the points of the " $table_mode " curve" ]
+                        $table_mode,
+                    )*
                 }
 
                 #[derive(clap::Subcommand)]
@@ -151,36 +379,110 @@ macro_rules! declare_params {
     };
 }
 
+/// Build a parameter's [`ParamInfo`], where the range and unit are optional.
+macro_rules! param_info {
+    (
+        $param:ident, $mode:expr, $description:expr,
+        {
+            default: $default:expr
+            $(, min: $min:expr)?
+            $(, max: $max:expr)?
+            $(, unit: $unit:literal)?
+            $(,)?
+        }
+    ) => {
+        ParamInfo {
+            param: Param::$param,
+            mode: $mode,
+            default: $default,
+            min: param_info!(@optional $($min)?),
+            max: param_info!(@optional $($max)?),
+            unit: param_info!(@optional $($unit)?),
+            description: $description.trim_ascii(),
+        }
+    };
+    (@optional) => { None };
+    (@optional $value:expr) => { Some($value) };
+}
+
 declare_params!(
     Common {
-        SensMult,
-        YxRatio,
-        InputDpi
+        /// A factor applied to the sensitivity, after the acceleration.
+        SensMult { default: 1.0 },
+        /// A factor applied to the sensitivity on the Y axis, on top of the sens multiplier.
+        YxRatio { default: 1.0 },
+        /// The DPI of the mouse, to normalize the input speed to that of a 1000 DPI mouse.
+        InputDpi { default: 1000.0, min: Bound::Exclusive(0.0), unit: "dpi" },
     },
     Linear {
-        AccelLinear,
-        OffsetLinear,
-        OutputCapLinear,
+        /// How fast the sensitivity grows with the input speed, past the offset.
+        AccelLinear { default: 0.0 },
+        /// The input speed past which the sensitivity starts to grow.
+        OffsetLinear { default: 0.0, min: Bound::Inclusive(0.0), unit: "counts/ms" },
+        /// The sensitivity the curve stops growing at, or 0 for no cap.
+        OutputCapLinear { default: 0.0 },
     },
     Classic {
-        AccelClassic,
-        PowerClassic,
-        OffsetClassic,
-        OutputCapClassic,
+        /// How fast the sensitivity grows with the input speed, past the offset.
+        AccelClassic { default: 0.0 },
+        /// The power the input speed past the offset is raised to, 2 being the same as Linear.
+        PowerClassic { default: 2.0 },
+        /// The input speed past which the sensitivity starts to grow.
+        OffsetClassic { default: 0.0, min: Bound::Inclusive(0.0), unit: "counts/ms" },
+        /// The sensitivity the curve stops growing at, or 0 for no cap.
+        OutputCapClassic { default: 0.0 },
     },
     Natural {
-        DecayRate,
-        OffsetNatural,
-        Limit,
+        /// How fast the sensitivity gets to the limit.
+        DecayRate { default: 0.1, min: Bound::Exclusive(0.0) },
+        /// The input speed past which the sensitivity starts to grow.
+        OffsetNatural { default: 0.0, min: Bound::Inclusive(0.0), unit: "counts/ms" },
+        /// The sensitivity the curve grows towards.
+        Limit { default: 1.5, min: Bound::Inclusive(1.0) },
     },
     Synchronous {
-        Gamma,
-        Smooth,
-        Motivity,
-        SyncSpeed,
+        /// How fast the sensitivity goes from low to high around the sync speed.
+        Gamma { default: 1.0, min: Bound::Exclusive(0.0) },
+        /// How suddenly the sensitivity changes, from 0 for not at all, to 1.
+        Smooth { default: 0.5, min: Bound::Inclusive(0.0), max: Bound::Inclusive(1.0) },
+        /// The highest sensitivity, its inverse being the lowest.
+        Motivity { default: 1.5, min: Bound::Exclusive(1.0) },
+        /// The input speed at which the sensitivity is between the lowest and the highest.
+        SyncSpeed { default: 5.0, min: Bound::Exclusive(0.0), unit: "counts/ms" },
     },
+    Jump {
+        /// The input speed at which the sensitivity jumps.
+        JumpSpeed { default: 15.0, min: Bound::Exclusive(0.0), unit: "counts/ms" },
+        /// The sensitivity past the jump, the sensitivity before it being 1.
+        JumpSens { default: 1.5, min: Bound::Exclusive(0.0) },
+        /// How gradual the jump is, as a fraction of the jump speed, from 0 for a sharp jump.
+        SmoothJump { default: 0.5, min: Bound::Inclusive(0.0), max: Bound::Inclusive(1.0) },
+    },
+    Power {
+        /// The factor the input speed is scaled by, before it's raised to the exponent.
+        ScalePower { default: 1.0, min: Bound::Exclusive(0.0) },
+        /// The exponent the scaled input speed is raised to.
+        ExponentPower { default: 0.05, min: Bound::Exclusive(0.0) },
+        /// The sensitivity the curve starts at, added to the power.
+        OutputOffsetPower { default: 0.0, min: Bound::Inclusive(0.0) },
+        /// The sensitivity the curve stops growing at, or 0 for no cap.
+        OutputCapPower { default: 0.0, min: Bound::Inclusive(0.0) },
+    },
+    Windows {
+        /// The notch of the pointer speed slider, from 1 to 11, rounded to the nearest one,
+        /// 6 being the middle one, at which the pointer's speed isn't scaled.
+        SliderWindows { default: 6.0, min: Bound::Inclusive(1.0), max: Bound::Inclusive(11.0) },
+        /// The refresh rate of the display, which Windows measures the mouse's speed with.
+        RefreshRateWindows { default: 60.0, min: Bound::Exclusive(0.0), unit: "Hz" },
+        /// The DPI of the display, which Windows turns the pointer's speed into pixels with.
+        ScreenDpiWindows { default: 96.0, min: Bound::Exclusive(0.0), unit: "dpi" },
+    },
+    Macos {
+        /// How much fast movements are sped up, from 0 for not at all, like macOS's slider.
+        TrackingSpeed { default: 1.0, min: Bound::Inclusive(0.0), max: Bound::Inclusive(3.0) },
+    };
+    LookupTable,
 );
-
 impl AccelMode {
     pub fn as_title(&self) -> &'static str {
         match self {
@@ -188,6 +490,11 @@ impl AccelMode {
             AccelMode::Classic => "Classic Acceleration",
             AccelMode::Natural => "Natural (w/ Gain)",
             AccelMode::Synchronous => "Synchronous",
+            AccelMode::Jump => "Jump",
+            AccelMode::Power => "Power",
+            AccelMode::Windows => "Windows (Enhance pointer precision)",
+            AccelMode::Macos => "macOS",
+            AccelMode::LookupTable => "Look-Up Table",
         }
     }
 }
@@ -198,205 +505,9 @@ impl AccelMode {
     pub fn ordinal(&self) -> i64 {
         (*self as i8).into()
     }
-}
-
-pub mod persist {
-    use std::{
-        fmt::Debug,
-        io::Read,
-        path::{Path, PathBuf},
-    };
-
-    use anyhow::{anyhow, Context};
-
-    use crate::fixedptc::Fpt;
-
-    use super::*;
-
-    pub trait ParamStore: Debug {
-        fn set(&mut self, param: super::Param, value: f64) -> anyhow::Result<()>;
-        fn get(&self, param: &super::Param) -> anyhow::Result<Fpt>;
-
-        fn set_current_accel_mode(mode: AccelMode);
-        fn get_current_accel_mode() -> AccelMode;
-    }
-
-    const SYS_MODULE_PATH: &str = "/sys/module/maccel";
-
-    #[derive(Debug)]
-    pub struct SysFsStore;
-
-    impl ParamStore for SysFsStore {
-        fn set(&mut self, param: super::Param, value: f64) -> anyhow::Result<()> {
-            use validate::validate_param_value;
-            validate_param_value(param, value)?;
-
-            let value: Fpt = value.into();
-            let value = value.0;
-            set_parameter(param.name(), value)
-        }
-
-        fn get(&self, param: &super::Param) -> anyhow::Result<Fpt> {
-            let value = get_paramater(param.name())?;
-            let value = Fpt::from_str(&value).context(format!(
-                "couldn't interpret the parameter's value {}",
-                value
-            ))?;
-            Ok(value)
-        }
-
-        fn set_current_accel_mode(mode: AccelMode) {
-            set_parameter(AccelMode::PARAM_NAME, mode.ordinal())
-                .expect("Failed to set kernel param to change modes");
-        }
-        fn get_current_accel_mode() -> AccelMode {
-            get_paramater(AccelMode::PARAM_NAME)
-                .map(|mode_tag| {
-                    let id: u8 = mode_tag
-                        .parse()
-                        .expect("Failed to parse an id for mode parameter");
-                    let idx = id as usize % ALL_MODES.len();
-                    ALL_MODES[idx]
-                })
-                .expect("Failed to read a kernel parameter to get the acceleration mode desired")
-        }
-    }
-
-    impl SysFsStore {
-        pub fn set_all_common(&mut self, args: CommonParamArgs) -> anyhow::Result<()> {
-            let CommonParamArgs {
-                sens_mult,
-                yx_ratio,
-                input_dpi,
-            } = args;
-
-            self.set(Param::SensMult, sens_mult)?;
-            self.set(Param::YxRatio, yx_ratio)?;
-            self.set(Param::InputDpi, input_dpi)?;
-
-            Ok(())
-        }
-
-        pub fn set_all_linear(&mut self, args: LinearParamArgs) -> anyhow::Result<()> {
-            let LinearParamArgs {
-                accel_linear,
-                offset_linear,
-                output_cap_linear,
-            } = args;
-
-            self.set(Param::AccelLinear, accel_linear)?;
-            self.set(Param::OffsetLinear, offset_linear)?;
-            self.set(Param::OutputCapLinear, output_cap_linear)?;
-
-            Ok(())
-        }
-        
-        pub fn set_all_classic(&mut self, args: ClassicParamArgs) -> anyhow::Result<()> {
-            let ClassicParamArgs {
-                accel_classic,
-                power_classic,
-                offset_classic,
-                output_cap_classic,
-            } = args;
-
-            self.set(Param::AccelClassic, accel_classic)?;
-            self.set(Param::PowerClassic, power_classic)?;
-            self.set(Param::OffsetClassic, offset_classic)?;
-            self.set(Param::OutputCapClassic, output_cap_classic)?;
-
-            Ok(())
-        }
-
-        pub fn set_all_natural(&mut self, args: NaturalParamArgs) -> anyhow::Result<()> {
-            let NaturalParamArgs {
-                decay_rate,
-                limit,
-                offset_natural,
-            } = args;
-
-            self.set(Param::DecayRate, decay_rate)?;
-            self.set(Param::OffsetNatural, offset_natural)?;
-            self.set(Param::Limit, limit)?;
-
-            Ok(())
-        }
-
-        pub fn set_all_synchronous(&mut self, args: SynchronousParamArgs) -> anyhow::Result<()> {
-            let SynchronousParamArgs {
-                gamma,
-                smooth,
-                motivity,
-                sync_speed,
-            } = args;
-
-            self.set(Param::Gamma, gamma)?;
-            self.set(Param::Smooth, smooth)?;
-            self.set(Param::Motivity, motivity)?;
-            self.set(Param::SyncSpeed, sync_speed)?;
-
-            Ok(())
-        }
-    }
-
-    fn parameter_path(name: &'static str) -> anyhow::Result<PathBuf> {
-        let params_path = Path::new(SYS_MODULE_PATH).join("parameters").join(name);
-
-        if !params_path.exists() {
-            return Err(anyhow!("no such path: {}", params_path.display()))
-                .context(anyhow!("no such parameter {:?}", name));
-        }
-
-        Ok(params_path)
-    }
-
-    fn save_parameter_reset_script(name: &'static str, value: i64) -> anyhow::Result<()> {
-        let script_dir = "/var/opt/maccel/resets";
-        if !Path::new(script_dir).exists() {
-            std::fs::create_dir_all(script_dir).context(format!("failed create directory: {}", script_dir))
-                .context("failed to create the directory where we'd save the parameter value to apply on reboot")?;
-        }
-        std::fs::write(
-            format!("{script_dir}/set_last_{}_value.sh", name),
-            format!("echo {} > /sys/module/maccel/parameters/{};", value, name),
-        )
-        .context("failed to write reset script")?;
-        Ok(())
-    }
-
-    fn get_paramater(name: &'static str) -> anyhow::Result<String> {
-        let path = parameter_path(name)?;
-        let mut file = std::fs::File::open(&path)
-            .context(anyhow!(
-                "failed to open the parameter's file for reading: {}",
-                path.display()
-            ))
-            .context("this shouldn't happen unless the maccel kernel module is not installed.")?;
 
-        let mut buf = String::new();
-
-        file.read_to_string(&mut buf)
-            .context("failed to read the parameter's value")?;
-
-        Ok(buf.trim().to_string())
-    }
-
-    fn set_parameter(name: &'static str, value: i64) -> anyhow::Result<()> {
-        let path = parameter_path(name)?;
-
-        std::fs::write(&path, format!("{}", value)).context(anyhow!(
-            "failed to write to parameter file: {}",
-            path.display()
-        ))?;
-
-        save_parameter_reset_script(name, value)?;
-
-        Ok(())
-    }
-
-    impl Display for Fpt {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.write_str(&format_param_value(f64::from(*self)))
-        }
+    pub fn from_key(key: &str) -> Option<Self> {
+        ALL_MODES.iter().copied().find(|m| m.key() == key)
     }
 }
 
@@ -408,20 +519,46 @@ impl Param {
             Param::SensMult => "SENS_MULT",
             Param::YxRatio => "YX_RATIO",
             Param::InputDpi => "INPUT_DPI",
-            Param::AccelLinear => "ACCEL",
-            Param::AccelClassic => "ACCEL",
+            Param::AccelLinear => "LINEAR_ACCEL",
+            Param::AccelClassic => "CLASSIC_ACCEL",
             Param::PowerClassic => "POWER",
-            Param::OffsetLinear => "OFFSET",
-            Param::OffsetClassic => "OFFSET",
-            Param::OffsetNatural => "OFFSET",
-            Param::OutputCapLinear => "OUTPUT_CAP",
-            Param::OutputCapClassic => "OUTPUT_CAP",
+            Param::OffsetLinear => "LINEAR_OFFSET",
+            Param::OffsetClassic => "CLASSIC_OFFSET",
+            Param::OffsetNatural => "NATURAL_OFFSET",
+            Param::OutputCapLinear => "LINEAR_OUTPUT_CAP",
+            Param::OutputCapClassic => "CLASSIC_OUTPUT_CAP",
             Param::DecayRate => "DECAY_RATE",
             Param::Limit => "LIMIT",
             Param::Gamma => "GAMMA",
             Param::Smooth => "SMOOTH",
             Param::Motivity => "MOTIVITY",
             Param::SyncSpeed => "SYNC_SPEED",
+            Param::JumpSpeed => "JUMP_SPEED",
+            Param::JumpSens => "JUMP_SENS",
+            Param::SmoothJump => "JUMP_SMOOTH",
+            Param::ScalePower => "POWER_SCALE",
+            Param::ExponentPower => "POWER_EXPONENT",
+            Param::OutputOffsetPower => "POWER_OUTPUT_OFFSET",
+            Param::OutputCapPower => "POWER_OUTPUT_CAP",
+            Param::SliderWindows => "WINDOWS_SLIDER",
+            Param::RefreshRateWindows => "WINDOWS_REFRESH_RATE",
+            Param::ScreenDpiWindows => "WINDOWS_SCREEN_DPI",
+            Param::TrackingSpeed => "MACOS_TRACKING_SPEED",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        ALL_PARAMS.iter().copied().find(|p| p.key() == key)
+    }
+
+    /// The name the kernel module used for the parameter before every mode had its own,
+    /// when the curves shared a single `ACCEL`, `OFFSET` and `OUTPUT_CAP`.
+    pub fn legacy_name(&self) -> &'static str {
+        match self {
+            Param::AccelLinear | Param::AccelClassic => "ACCEL",
+            Param::OffsetLinear | Param::OffsetClassic | Param::OffsetNatural => "OFFSET",
+            Param::OutputCapLinear | Param::OutputCapClassic => "OUTPUT_CAP",
+            _ => self.name(),
         }
     }
 
@@ -444,11 +581,109 @@ impl Param {
             Param::Smooth => "Smooth",
             Param::Motivity => "Motivity",
             Param::SyncSpeed => "Sync Speed",
+            Param::JumpSpeed => "Jump Speed",
+            Param::JumpSens => "Jump Sens",
+            Param::SmoothJump => "Smooth",
+            Param::ScalePower => "Scale",
+            Param::ExponentPower => "Exponent",
+            Param::OutputOffsetPower => "Output-Offset",
+            Param::OutputCapPower => "Output-Cap",
+            Param::SliderWindows => "Slider",
+            Param::RefreshRateWindows => "Refresh Rate",
+            Param::ScreenDpiWindows => "Screen DPI",
+            Param::TrackingSpeed => "Tracking Speed",
         }
     }
 }
 
-fn format_param_value(value: f64) -> String {
+/// One end of the range of values a parameter takes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Inclusive(f64),
+    Exclusive(f64),
+}
+
+/// What a parameter means, and the values it takes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamInfo {
+    pub param: Param,
+    /// The mode whose curve the parameter shapes, or `None` for the common parameters.
+    pub mode: Option<AccelMode>,
+    /// The value the driver starts with, as in `driver/params.h`.
+    pub default: f64,
+    pub min: Option<Bound>,
+    pub max: Option<Bound>,
+    pub unit: Option<&'static str>,
+    pub description: &'static str,
+}
+
+impl ParamInfo {
+    /// Whether the value is in the parameter's range.
+    pub fn contains(&self, value: f64) -> bool {
+        let above_min = match self.min {
+            Some(Bound::Inclusive(min)) => value >= min,
+            Some(Bound::Exclusive(min)) => value > min,
+            None => true,
+        };
+        let below_max = match self.max {
+            Some(Bound::Inclusive(max)) => value <= max,
+            Some(Bound::Exclusive(max)) => value < max,
+            None => true,
+        };
+
+        above_min && below_max
+    }
+
+    /// The range of values, as in "must be ...", or `None` if it takes any value.
+    pub fn range(&self) -> Option<String> {
+        let min = self.min.map(|min| match min {
+            Bound::Inclusive(min) => format!("at least {}", format_param_value(min)),
+            Bound::Exclusive(min) => format!("greater than {}", format_param_value(min)),
+        });
+        let max = self.max.map(|max| match max {
+            Bound::Inclusive(max) => format!("at most {}", format_param_value(max)),
+            Bound::Exclusive(max) => format!("less than {}", format_param_value(max)),
+        });
+
+        match (self.min, self.max) {
+            (Some(Bound::Inclusive(min)), Some(Bound::Inclusive(max))) => Some(format!(
+                "between {} and {}",
+                format_param_value(min),
+                format_param_value(max)
+            )),
+            _ => match (min, max) {
+                (Some(min), Some(max)) => Some(format!("{min} and {max}")),
+                (min, max) => min.or(max),
+            },
+        }
+    }
+
+    /// The description, followed by the unit, range and default value.
+    pub fn help(&self) -> String {
+        let mut details = vec![];
+        if let Some(unit) = self.unit {
+            details.push(format!("in {unit}"));
+        }
+        if let Some(range) = self.range() {
+            details.push(range);
+        }
+        details.push(format!("default: {}", format_param_value(self.default)));
+
+        format!(
+            "{} [{}]",
+            self.description.trim_end_matches('.'),
+            details.join(", ")
+        )
+    }
+}
+
+impl Param {
+    pub fn info(&self) -> &'static ParamInfo {
+        &ALL_PARAM_INFO[*self as usize]
+    }
+}
+
+pub(crate) fn format_param_value(value: f64) -> String {
     let mut number = format!("{:.5}", value);
 
     for idx in (1..number.len()).rev() {
@@ -475,58 +710,101 @@ fn format_param_value_works() {
     assert_eq!(format_param_value(0.055000), "0.055");
 }
 
-mod validate {
+#[cfg(test)]
+#[test]
+fn param_info_matches_the_driver() {
+    let params_h = include_str!("../../../driver/params.h");
+
+    for (info, param) in ALL_PARAM_INFO.iter().zip(ALL_PARAMS) {
+        assert_eq!(info.param, *param);
+        assert_eq!(
+            info.mode.is_some_and(|mode| mode.params().contains(param)),
+            !ALL_COMMON_PARAMS.contains(param),
+            "{param:?}"
+        );
+        assert!(info.contains(info.default), "{param:?}");
+
+        // Every `PARAM(NAME, raw_default, ...)`, one for each width the driver can be built with.
+        let defaults: Vec<i64> = params_h
+            .split("PARAM(")
+            .skip(1)
+            .filter_map(|decl| {
+                let (name, rest) = decl.split_once(',')?;
+                let raw = rest
+                    .trim_start()
+                    .split(|c: char| !c.is_ascii_digit())
+                    .next()?;
+                (name.trim() == param.name()).then(|| raw.parse().unwrap())
+            })
+            .collect();
+        assert!(!defaults.is_empty(), "{param:?}");
+        let raw_for = |fbits: u32| (info.default * (1i64 << fbits) as f64).round() as i64;
+        for raw in defaults {
+            assert!(raw == raw_for(16) || raw == raw_for(32), "{param:?}: {raw}");
+        }
+    }
+
+    let smooth = Param::Smooth.info();
+    assert!(!smooth.contains(1.5));
+    assert_eq!(smooth.range().as_deref(), Some("between 0 and 1"));
+    assert_eq!(
+        Param::SyncSpeed.info().help(),
+        "The input speed at which the sensitivity is between the lowest and the highest \
+         [in counts/ms, greater than 0, default: 5]"
+    );
+}
+
+#[cfg(all(test, feature = "serde"))]
+#[test]
+fn serde_names_are_the_stable_keys() {
+    for param in ALL_PARAMS {
+        assert_eq!(
+            toml::Value::try_from(param).unwrap().as_str(),
+            Some(param.key())
+        );
+    }
+    for mode in ALL_MODES {
+        assert_eq!(
+            toml::Value::try_from(mode).unwrap().as_str(),
+            Some(mode.key())
+        );
+    }
+
+    let args =
+        AllParamArgs::try_from_fn(|p| Ok(Fpt::from(if p == Param::Limit { 1.5 } else { 2.0 })))
+            .unwrap();
+    let serialized = toml::to_string(&args).unwrap();
+    assert!(serialized.contains("sens_mult = 2.0\n"));
+    assert!(serialized.contains("limit = 1.5\n"));
+
+    let deserialized: AllParamArgs = toml::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.get(Param::Limit), Fpt::from(1.5));
+
+    let linear: LinearParamArgs =
+        toml::from_str("accel_linear = 0.3\noffset_linear = 2.0\noutput_cap_linear = 0.0").unwrap();
+    assert_eq!(linear.accel_linear, 0.3);
+}
+
+pub(crate) mod validate {
     use super::Param;
+    use crate::Error;
+    use crate::fixedptc::{FIXEDPT_BITS, Fpt};
+
+    pub fn validate_param_value(param_tag: Param, value: f64) -> crate::Result<()> {
+        if Fpt::checked_from(value).is_none() {
+            return Err(Error::invalid_value(
+                param_tag,
+                format!("{value} cannot be represented as a {FIXEDPT_BITS}-bit fixed-point number"),
+            ));
+        }
 
-    pub fn validate_param_value(param_tag: Param, value: f64) -> anyhow::Result<()> {
-        match param_tag {
-            Param::SensMult => {}
-            Param::YxRatio => {}
-            Param::InputDpi => {
-                if value <= 0.0 {
-                    anyhow::bail!("Input DPI must be positive");
-                }
-            }
-            Param::AccelLinear => {}
-            Param::OutputCapLinear => {}
-            Param::OffsetLinear | Param::OffsetClassic | Param::OffsetNatural => {
-                if value < 0.0 {
-                    anyhow::bail!("offset cannot be less than 0");
-                }
-            }
-            Param::AccelClassic => {}
-            Param::PowerClassic => {}
-            Param::OutputCapClassic => {}
-            Param::DecayRate => {
-                if value <= 0.0 {
-                    anyhow::bail!("decay rate must be positive");
-                }
-            }
-            Param::Limit => {
-                if value < 1.0 {
-                    anyhow::bail!("limit cannot be less than 1");
-                }
-            }
-            Param::Gamma => {
-                if value <= 0.0 {
-                    anyhow::bail!("Gamma must be positive");
-                }
-            }
-            Param::Smooth => {
-                if !(0.0..=1.0).contains(&value) {
-                    anyhow::bail!("Smooth must be between 0 and 1");
-                }
-            }
-            Param::Motivity => {
-                if value <= 1.0 {
-                    anyhow::bail!("Motivity must be greater than 1");
-                }
-            }
-            Param::SyncSpeed => {
-                if value <= 0.0 {
-                    anyhow::bail!("'Synchronous speed' must be positive");
-                }
-            }
+        let info = param_tag.info();
+        if !info.contains(value) {
+            let range = info.range().unwrap_or_default();
+            return Err(Error::invalid_value(
+                param_tag,
+                format!("{} must be {range}", param_tag.display_name()),
+            ));
         }
 
         Ok(())
@@ -0,0 +1,511 @@
+use std::{
+    fmt::{Debug, Display},
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::fixedptc::Fpt;
+use crate::params::{format_param_value, validate};
+use crate::validation::{Validation, validate_params};
+use crate::*;
+
+mod file;
+mod modprobe;
+mod profile;
+mod transaction;
+
+pub use file::FileStore;
+pub use modprobe::{MODPROBE_OPTIONS_PATH, modprobe_options, write_modprobe_options};
+pub use profile::Profiles;
+pub use transaction::Transaction;
+
+pub trait ParamStore: Debug {
+    fn set(&mut self, param: Param, value: f64) -> crate::Result<()>;
+    fn get(&self, param: &Param) -> crate::Result<Fpt>;
+
+    /// Write `value` as is, without validating it, to put back a value `param` had,
+    /// e.g. when rolling back a [`Transaction`].
+    fn set_unchecked(&mut self, param: Param, value: Fpt) -> crate::Result<()>;
+
+    fn set_current_accel_mode(&mut self, mode: AccelMode) -> crate::Result<()>;
+    fn get_current_accel_mode(&self) -> crate::Result<AccelMode>;
+
+    /// The points of the [`AccelMode::LookupTable`] curve, which aren't a parameter like the others.
+    fn set_lookup_table(&mut self, table: &LookupTable) -> crate::Result<()>;
+    fn get_lookup_table(&self) -> crate::Result<LookupTable>;
+
+    /// Validate every value in `transaction`, and the parameters as they would be together,
+    /// then apply them and its mode, rolling back to the previous values and mode if anything fails.
+    /// Gives back the warnings, see [`Transaction::check`].
+    fn apply(&mut self, transaction: &Transaction) -> crate::Result<Validation> {
+        transaction.apply_to(self)
+    }
+
+    fn set_all_common(&mut self, args: CommonParamArgs) -> crate::Result<()> {
+        self.apply(&args.into())?;
+        Ok(())
+    }
+
+    fn set_all_linear(&mut self, args: LinearParamArgs) -> crate::Result<()> {
+        self.apply(&args.into())?;
+        Ok(())
+    }
+
+    fn set_all_classic(&mut self, args: ClassicParamArgs) -> crate::Result<()> {
+        self.apply(&args.into())?;
+        Ok(())
+    }
+
+    fn set_all_natural(&mut self, args: NaturalParamArgs) -> crate::Result<()> {
+        self.apply(&args.into())?;
+        Ok(())
+    }
+
+    fn set_all_synchronous(&mut self, args: SynchronousParamArgs) -> crate::Result<()> {
+        self.apply(&args.into())?;
+        Ok(())
+    }
+
+    fn set_all_lookup_table(&mut self, args: LookupTableParamArgs) -> crate::Result<()> {
+        self.apply(&args.into())?;
+        Ok(())
+    }
+
+    fn get_all(&self) -> crate::Result<AllParamArgs> {
+        let mut args = AllParamArgs::try_from_fn(|p| self.get(&p))?;
+        args.lookup_table.points = self.get_lookup_table()?;
+        Ok(args)
+    }
+
+    fn set_all(&mut self, args: &AllParamArgs) -> crate::Result<()> {
+        self.apply(&Transaction::from(args))?;
+        Ok(())
+    }
+}
+
+/// The name the maccel kernel module is loaded as.
+pub const DEFAULT_MODULE_NAME: &str = "maccel";
+
+/// Where we keep every parameter's last set value, and the mode, to apply on reboot.
+const STATE_FILE_PATH: &str = "var/opt/maccel/state.toml";
+
+/// Where older versions saved a shell script per parameter to apply on reboot.
+const LEGACY_RESET_SCRIPTS_DIR: &str = "var/opt/maccel/resets";
+
+#[derive(Debug, Clone)]
+pub struct SysFsStore {
+    root: PathBuf,
+    module_path: PathBuf,
+    /// Whether writes are saved to the state file too, which they aren't when we restore from it.
+    saves_state: bool,
+}
+
+impl Default for SysFsStore {
+    fn default() -> Self {
+        Self::new("/", DEFAULT_MODULE_NAME)
+    }
+}
+
+impl ParamStore for SysFsStore {
+    fn set(&mut self, param: Param, value: f64) -> crate::Result<()> {
+        use validate::validate_param_value;
+        validate_param_value(param, value)?;
+
+        let fpt: Fpt = value.into();
+        self.set_parameter(param.name(), fpt.0)?;
+
+        self.save_state(|state| state.set(param, value))
+    }
+
+    fn set_unchecked(&mut self, param: Param, value: Fpt) -> crate::Result<()> {
+        self.set_parameter(param.name(), value.0)?;
+        self.save_state(|state| state.set_unchecked(param, value))
+    }
+
+    fn get(&self, param: &Param) -> crate::Result<Fpt> {
+        let value = self.get_paramater(param.name())?;
+        Fpt::from_str(&value).map_err(|_| {
+            Error::parse(
+                self.module_path.join("parameters").join(param.name()),
+                format!("{value:?} is not a fixed-point number"),
+            )
+        })
+    }
+
+    fn set_current_accel_mode(&mut self, mode: AccelMode) -> crate::Result<()> {
+        self.set_parameter(AccelMode::PARAM_NAME, mode.ordinal())?;
+        self.save_state(|state| state.set_current_accel_mode(mode))
+    }
+
+    fn get_current_accel_mode(&self) -> crate::Result<AccelMode> {
+        let mode_tag = self.get_paramater(AccelMode::PARAM_NAME)?;
+
+        mode_tag
+            .parse::<usize>()
+            .ok()
+            .and_then(|id| ALL_MODES.get(id))
+            .copied()
+            .ok_or(Error::UnknownMode(mode_tag))
+    }
+
+    fn set_lookup_table(&mut self, table: &LookupTable) -> crate::Result<()> {
+        // Without points, writing nothing wouldn't reach the module.
+        let points = format!("{}\n", table.to_driver_string());
+        self.set_parameter(LookupTable::PARAM_NAME, points)?;
+        self.save_state(|state| state.set_lookup_table(table))
+    }
+
+    /// Applied to the module like any store, see [`Transaction::apply_to`],
+    /// then saved to the state file in one write, rather than one per value.
+    fn apply(&mut self, transaction: &Transaction) -> crate::Result<Validation> {
+        let validation = transaction.apply_to(&mut self.module())?;
+        self.save_state(|state| state.record(transaction))?;
+        Ok(validation)
+    }
+
+    fn get_lookup_table(&self) -> crate::Result<LookupTable> {
+        let value = self.get_paramater(LookupTable::PARAM_NAME)?;
+        LookupTable::from_driver_string(&value).map_err(|err| {
+            Error::parse(
+                self.module_path
+                    .join("parameters")
+                    .join(LookupTable::PARAM_NAME),
+                err,
+            )
+        })
+    }
+}
+
+impl SysFsStore {
+    /// Work on the parameters of the kernel module loaded as `module_name`,
+    /// with every path resolved under `root` instead of `/`,
+    /// e.g. `<root>/sys/module/<module_name>/parameters`.
+    pub fn new(root: impl AsRef<Path>, module_name: &str) -> Self {
+        let root = root.as_ref();
+        Self {
+            root: root.to_path_buf(),
+            module_path: root.join("sys/module").join(module_name),
+            saves_state: true,
+        }
+    }
+
+    /// Where the parameters and mode are saved to apply on reboot.
+    pub fn state_file_path(&self) -> PathBuf {
+        self.root.join(STATE_FILE_PATH)
+    }
+
+    /// Apply the parameters and mode saved in the state file, e.g. when the module is loaded on boot.
+    /// Every saved value is validated before anything is written to the module,
+    /// and the module is rolled back to the values it had if any write fails, see [`Transaction`].
+    ///
+    /// A saved curve that's broken, see [`validate_params`], is left out rather than keeping
+    /// the others from being restored, as are the values of the legacy reset scripts we'd reject,
+    /// see [`Self::migrate_legacy_reset_scripts`]. Gives back why each was left out.
+    pub fn restore(&mut self) -> crate::Result<Vec<Error>> {
+        let state_file_path = self.state_file_path();
+
+        let mut left_out = vec![];
+        if !state_file_path.exists() {
+            left_out = self.migrate_legacy_reset_scripts()?;
+        }
+
+        if !state_file_path.exists() {
+            return Ok(left_out);
+        }
+
+        let state = FileStore::open(state_file_path)?;
+        let args = state.get_all()?;
+        let saved_mode = state.get_current_accel_mode()?;
+
+        let mut broken_modes = vec![];
+        for &mode in ALL_MODES {
+            if let Err(err) = validate_params(mode, &args).into_result() {
+                broken_modes.push(mode);
+                left_out.push(err);
+            }
+        }
+
+        // Every curve has its own slots in the module, so we restore all of them,
+        // not just the saved mode's.
+        let mut transaction = ALL_PARAMS
+            .iter()
+            .filter(|p| !broken_modes.iter().any(|m| m.params().contains(p)))
+            .fold(Transaction::new(), |tx, &p| tx.set(p, args.get(p).into()));
+
+        // Older states don't have one, nor need a module that takes one.
+        if !args.lookup_table.points.is_empty() {
+            transaction = transaction.lookup_table(args.lookup_table.points.clone());
+        }
+
+        if !broken_modes.contains(&saved_mode) {
+            transaction = transaction.mode(saved_mode);
+        }
+
+        // The state file already has these values, and must keep them if we roll back.
+        transaction.apply_to(&mut self.module())?;
+
+        Ok(left_out)
+    }
+
+    /// The same module, without saving what's written to the state file.
+    fn module(&self) -> Self {
+        Self {
+            saves_state: false,
+            ..self.clone()
+        }
+    }
+
+    fn save_state(
+        &self,
+        update: impl FnOnce(&mut FileStore) -> crate::Result<()>,
+    ) -> crate::Result<()> {
+        if !self.saves_state {
+            return Ok(());
+        }
+
+        let state_file_path = self.state_file_path();
+
+        // A state file we can't make sense of, e.g. one left half-written by an older version,
+        // is started over like a new one, rather than keeping anything from being saved.
+        let existing = match FileStore::open(&state_file_path) {
+            Ok(state) if state_file_path.exists() => Some(state),
+            Ok(_) | Err(Error::Parse { .. }) => None,
+            Err(err) => return Err(err),
+        };
+
+        let mut state = match existing {
+            Some(state) => state,
+            None => {
+                // Best effort to start off from everything that's live,
+                // a value the module has but we'd reject shouldn't keep us from saving the others.
+                let mut live = Transaction::new().mode(self.get_current_accel_mode()?);
+                for &param in ALL_PARAMS {
+                    let Ok(value) = self.get(&param).map(f64::from) else {
+                        continue;
+                    };
+                    if validate::validate_param_value(param, value).is_ok() {
+                        live = live.set(param, value);
+                    }
+                }
+                if let Ok(table) = self.get_lookup_table() {
+                    live = live.lookup_table(table);
+                }
+
+                let mut state = FileStore::empty(state_file_path);
+                state.record(&live)?;
+                state
+            }
+        };
+
+        update(&mut state)
+    }
+
+    /// Build the state file from the `echo <value> > /sys/module/maccel/parameters/<NAME>`
+    /// scripts that older versions saved for each parameter, in one write once they're all read.
+    /// The values we'd reject are left out, and given back.
+    ///
+    /// Back then the curves shared the `ACCEL`, `OFFSET` and `OUTPUT_CAP` slots,
+    /// so their scripts only give us the values for the saved mode.
+    fn migrate_legacy_reset_scripts(&self) -> crate::Result<Vec<Error>> {
+        let dir = self.root.join(LEGACY_RESET_SCRIPTS_DIR);
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let saved_value = |name: &str| -> Option<i64> {
+            let script =
+                std::fs::read_to_string(dir.join(format!("set_last_{name}_value.sh"))).ok()?;
+            script
+                .strip_prefix("echo ")?
+                .split_whitespace()
+                .next()?
+                .parse()
+                .ok()
+        };
+
+        let mode = saved_value(AccelMode::PARAM_NAME)
+            .and_then(|id| ALL_MODES.get(id as usize))
+            .copied()
+            .unwrap_or_default();
+
+        let mut transaction = Transaction::new().mode(mode);
+        let mut left_out = vec![];
+        for &param in ALL_PARAMS {
+            let was_shared = param.legacy_name() != param.name();
+            if was_shared && !mode.params().contains(&param) {
+                continue;
+            }
+
+            if let Some(value) = saved_value(param.legacy_name()) {
+                let value = Fpt(value).into();
+                match validate::validate_param_value(param, value) {
+                    Ok(()) => transaction = transaction.set(param, value),
+                    Err(err) => left_out.push(err),
+                }
+            }
+        }
+
+        FileStore::empty(self.state_file_path()).record(&transaction)?;
+        Ok(left_out)
+    }
+
+    fn parameter_path(&self, name: &'static str) -> crate::Result<PathBuf> {
+        if !self.module_path.exists() {
+            return Err(Error::ModuleNotLoaded {
+                module_path: self.module_path.clone(),
+            });
+        }
+
+        let params_path = self.module_path.join("parameters").join(name);
+        if !params_path.exists() {
+            return Err(Error::UnsupportedParameter { name });
+        }
+
+        Ok(params_path)
+    }
+
+    fn get_paramater(&self, name: &'static str) -> crate::Result<String> {
+        let path = self.parameter_path(name)?;
+
+        let mut buf = String::new();
+        std::fs::File::open(&path)
+            .and_then(|mut file| file.read_to_string(&mut buf))
+            .map_err(|err| Error::io(&path, err))?;
+
+        Ok(buf.trim().to_string())
+    }
+
+    fn set_parameter(&self, name: &'static str, value: impl Display) -> crate::Result<()> {
+        let path = self.parameter_path(name)?;
+
+        std::fs::write(&path, format!("{}", value)).map_err(|err| Error::io(&path, err))
+    }
+}
+
+impl Display for Fpt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_param_value(f64::from(*self)))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn sysfs_store_works_on_a_fake_tree_under_any_root() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let params_dir = root.join("sys/module/maccel2/parameters");
+    std::fs::create_dir_all(&params_dir).unwrap();
+
+    for &param in ALL_PARAMS {
+        std::fs::write(params_dir.join(param.name()), Fpt::from(1.0).0.to_string()).unwrap();
+    }
+    std::fs::write(params_dir.join(AccelMode::PARAM_NAME), "0").unwrap();
+
+    assert!(matches!(
+        SysFsStore::new(root, "maccel").get(&Param::SensMult),
+        Err(Error::ModuleNotLoaded { .. })
+    ));
+
+    let mut store = SysFsStore::new(root, "maccel2");
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Linear);
+
+    store.set(Param::SensMult, 2.5).unwrap();
+    store.set(Param::AccelLinear, 0.3).unwrap();
+    store.set_current_accel_mode(AccelMode::Classic).unwrap();
+    store.set(Param::AccelClassic, 0.1).unwrap();
+    assert_eq!(store.get(&Param::SensMult).unwrap(), Fpt::from(2.5));
+    assert_eq!(store.get(&Param::AccelLinear).unwrap(), Fpt::from(0.3));
+    assert!(store.state_file_path().starts_with(root));
+
+    for param in [Param::SensMult, Param::AccelLinear, Param::AccelClassic] {
+        std::fs::write(params_dir.join(param.name()), "0").unwrap();
+    }
+    std::fs::write(params_dir.join(AccelMode::PARAM_NAME), "0").unwrap();
+
+    assert!(store.restore().unwrap().is_empty());
+    assert_eq!(store.get(&Param::SensMult).unwrap(), Fpt::from(2.5));
+    assert_eq!(store.get(&Param::AccelLinear).unwrap(), Fpt::from(0.3));
+    assert_eq!(store.get(&Param::AccelClassic).unwrap(), Fpt::from(0.1));
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Classic);
+
+    // A broken curve is left out, and the others are still restored.
+    store.set(Param::PowerClassic, 0.5).unwrap();
+    store.set(Param::OffsetClassic, 2.0).unwrap();
+    std::fs::write(params_dir.join(Param::PowerClassic.name()), "0").unwrap();
+    std::fs::write(params_dir.join(Param::SensMult.name()), "0").unwrap();
+    std::fs::write(params_dir.join(AccelMode::PARAM_NAME), "0").unwrap();
+    assert!(matches!(
+        store.restore().unwrap().as_slice(),
+        [Error::InvalidCurve(_)]
+    ));
+    assert_eq!(store.get(&Param::SensMult).unwrap(), Fpt::from(2.5));
+    assert_eq!(store.get(&Param::PowerClassic).unwrap(), Fpt(0));
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Linear);
+
+    // A state file cut short doesn't keep the module's values from being saved,
+    // it's started over from them.
+    std::fs::write(store.state_file_path(), "version = 1\nmode = \"clas").unwrap();
+    store
+        .apply(&Transaction::new().set(Param::SensMult, 1.5))
+        .unwrap();
+    let state = FileStore::open(store.state_file_path()).unwrap();
+    assert_eq!(state.get(&Param::SensMult).unwrap(), Fpt::from(1.5));
+    assert_eq!(state.get(&Param::AccelLinear).unwrap(), Fpt::from(0.3));
+    store.set(Param::PowerClassic, 2.0).unwrap();
+
+    // Nothing is written if any of the saved values is invalid.
+    let mut state = FileStore::open(store.state_file_path()).unwrap();
+    state.set_unchecked(Param::Limit, Fpt::from(0.5)).unwrap();
+    std::fs::write(params_dir.join(Param::SensMult.name()), "0").unwrap();
+    assert!(store.restore().is_err());
+    assert_eq!(store.get(&Param::SensMult).unwrap(), Fpt(0));
+}
+
+#[cfg(test)]
+#[test]
+fn restore_migrates_the_legacy_single_slot_reset_scripts() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let params_dir = root.join("sys/module/maccel/parameters");
+    let scripts_dir = root.join(LEGACY_RESET_SCRIPTS_DIR);
+    std::fs::create_dir_all(&params_dir).unwrap();
+    std::fs::create_dir_all(&scripts_dir).unwrap();
+
+    for &param in ALL_PARAMS {
+        std::fs::write(params_dir.join(param.name()), "0").unwrap();
+    }
+    std::fs::write(params_dir.join(AccelMode::PARAM_NAME), "0").unwrap();
+
+    let script = |name: &str, value: i64| {
+        std::fs::write(
+            scripts_dir.join(format!("set_last_{name}_value.sh")),
+            format!("echo {value} > /sys/module/maccel/parameters/{name}"),
+        )
+        .unwrap();
+    };
+    script("ACCEL", Fpt::from(0.5).0);
+    script("POWER", Fpt::from(3.0).0);
+    script("LIMIT", Fpt::from(0.5).0);
+    script(AccelMode::PARAM_NAME, AccelMode::Classic.ordinal());
+
+    // The value we'd reject is left out, without keeping the others from being migrated.
+    let mut store = SysFsStore::new(root, DEFAULT_MODULE_NAME);
+    assert!(matches!(
+        store.restore().unwrap().as_slice(),
+        [Error::InvalidValue {
+            param: Param::Limit,
+            ..
+        }]
+    ));
+
+    assert!(store.state_file_path().exists());
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Classic);
+    assert_eq!(store.get(&Param::AccelClassic).unwrap(), Fpt::from(0.5));
+    assert_eq!(store.get(&Param::PowerClassic).unwrap(), Fpt::from(3.0));
+    assert_eq!(store.get(&Param::AccelLinear).unwrap(), Fpt(0));
+    assert_eq!(
+        store.get(&Param::Limit).unwrap(),
+        Fpt::from(Param::Limit.info().default)
+    );
+}
@@ -0,0 +1,313 @@
+//! A [`ParamStore`] that keeps the parameters in a human-editable TOML file,
+//! e.g.
+//!
+//! ```toml
+//! version = 1
+//! mode = "linear"
+//! lookup_table = [[0.0, 1.0], [8.0, 1.5]]
+//!
+//! [parameters]
+//! sens_mult = 1.0
+//! accel_linear = 0.3
+//! ```
+//!
+//! Parameters are identified by [`Param::key`] and the mode by [`AccelMode::key`].
+//! The look-up table's points, as `[input speed, sensitivity]`, are left out when there are none.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    ALL_PARAMS, AccelMode, Error, LookupTable, Param, fixedptc::Fpt,
+    params::validate::validate_param_value, validation::Validation,
+};
+
+use super::{ParamStore, Transaction};
+
+/// The version of the file's format, bump it when making incompatible changes.
+const FORMAT_VERSION: i64 = 1;
+
+const VERSION_KEY: &str = "version";
+const MODE_KEY: &str = "mode";
+const PARAMETERS_KEY: &str = "parameters";
+const LOOKUP_TABLE_KEY: &str = "lookup_table";
+
+#[derive(Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    mode: AccelMode,
+    values: Vec<(Param, f64)>,
+    lookup_table: LookupTable,
+}
+
+impl FileStore {
+    /// Load the parameters from the file at `path`.
+    /// If the file doesn't exist yet, it is created on the first write.
+    pub fn open(path: impl Into<PathBuf>) -> crate::Result<Self> {
+        let path = path.into();
+
+        if !path.exists() {
+            return Ok(Self::empty(path));
+        }
+
+        let content = fs::read_to_string(&path).map_err(|err| Error::io(&path, err))?;
+        parse(path, &content)
+    }
+
+    /// Without any of the values the file at `path` might have, which it's replaced with
+    /// on the first write.
+    pub(super) fn empty(path: PathBuf) -> Self {
+        Self {
+            path,
+            mode: AccelMode::default(),
+            values: vec![],
+            lookup_table: LookupTable::default(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn put(&mut self, param: Param, value: f64) {
+        match self.values.iter_mut().find(|(p, _)| *p == param) {
+            Some((_, v)) => *v = value,
+            None => self.values.push((param, value)),
+        }
+    }
+
+    /// Write the values, mode and look-up table of `transaction`, as they are,
+    /// to the file at once, keeping the previous ones if that fails.
+    pub(super) fn record(&mut self, transaction: &Transaction) -> crate::Result<()> {
+        let previous_values = self.values.clone();
+        let previous_table = self.lookup_table.clone();
+        let previous_mode = self.mode;
+
+        for &(param, value) in transaction.values() {
+            self.put(param, value);
+        }
+        if let Some(table) = transaction.points() {
+            self.lookup_table = table.clone();
+        }
+        if let Some(mode) = transaction.accel_mode() {
+            self.mode = mode;
+        }
+
+        self.save().inspect_err(|_| {
+            self.values = previous_values;
+            self.lookup_table = previous_table;
+            self.mode = previous_mode;
+        })
+    }
+
+    /// Write to a file next to it, then move that over it,
+    /// so the file is never left half-written, e.g. by a crash.
+    fn save(&self) -> crate::Result<()> {
+        let dir = self.path.parent().filter(|d| !d.as_os_str().is_empty());
+        if let Some(dir) = dir {
+            fs::create_dir_all(dir).map_err(|err| Error::io(dir, err))?;
+        }
+
+        let mut temp_name = self.path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = self.path.with_file_name(temp_name);
+
+        fs::File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(self.to_toml_string().as_bytes())?;
+                file.sync_all()
+            })
+            .map_err(|err| Error::io(&temp_path, err))?;
+        fs::rename(&temp_path, &self.path).map_err(|err| Error::io(&self.path, err))
+    }
+
+    fn to_toml_string(&self) -> String {
+        let mut parameters = toml::Table::new();
+        for param in ALL_PARAMS {
+            if let Some((_, value)) = self.values.iter().find(|(p, _)| p == param) {
+                parameters.insert(param.key().to_string(), toml::Value::Float(*value));
+            }
+        }
+
+        let mut table = toml::Table::new();
+        table.insert(VERSION_KEY.to_string(), FORMAT_VERSION.into());
+        table.insert(MODE_KEY.to_string(), self.mode.key().into());
+        if !self.lookup_table.is_empty() {
+            let points: toml::value::Array = self
+                .lookup_table
+                .points()
+                .iter()
+                .map(|&(speed, sens)| toml::Value::from(vec![speed, sens]))
+                .collect();
+            table.insert(LOOKUP_TABLE_KEY.to_string(), points.into());
+        }
+        table.insert(PARAMETERS_KEY.to_string(), parameters.into());
+
+        table.to_string()
+    }
+}
+
+fn parse(path: PathBuf, content: &str) -> crate::Result<FileStore> {
+    let invalid = |reason: String| Error::parse(&path, reason);
+    let table: toml::Table = content.parse().map_err(|err| invalid(format!("{err}")))?;
+
+    let mut mode = AccelMode::default();
+    let mut values = vec![];
+    let mut lookup_table = LookupTable::default();
+
+    for (key, value) in table {
+        match key.as_str() {
+            VERSION_KEY => {
+                let version = value
+                    .as_integer()
+                    .ok_or_else(|| invalid("the version should be an integer".into()))?;
+                if version != FORMAT_VERSION {
+                    return Err(invalid(format!(
+                        "unsupported version {version}, expected {FORMAT_VERSION}"
+                    )));
+                }
+            }
+            MODE_KEY => {
+                let name = value
+                    .as_str()
+                    .ok_or_else(|| invalid("the mode should be a string".into()))?;
+                mode = AccelMode::from_key(name)
+                    .ok_or_else(|| invalid(format!("unknown mode {name:?}")))?;
+            }
+            LOOKUP_TABLE_KEY => {
+                let number = |value: &toml::Value| {
+                    value
+                        .as_float()
+                        .or_else(|| value.as_integer().map(|v| v as f64))
+                };
+                let points = value
+                    .as_array()
+                    .and_then(|points| {
+                        points
+                            .iter()
+                            .map(|point| match point.as_array()?.as_slice() {
+                                [speed, sens] => Some((number(speed)?, number(sens)?)),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or_else(|| {
+                        invalid("the look-up table should be a list of [speed, sensitivity]".into())
+                    })?;
+
+                lookup_table = LookupTable::new(&points).map_err(|err| invalid(err.to_string()))?;
+            }
+            PARAMETERS_KEY => {
+                let parameters = value
+                    .as_table()
+                    .ok_or_else(|| invalid("the parameters should be a table".into()))?;
+
+                for (name, value) in parameters {
+                    let param = Param::from_key(name)
+                        .ok_or_else(|| invalid(format!("unknown parameter {name:?}")))?;
+                    let value = value
+                        .as_float()
+                        .or_else(|| value.as_integer().map(|v| v as f64))
+                        .ok_or_else(|| {
+                            invalid(format!("the value of {name:?} should be a number"))
+                        })?;
+
+                    validate_param_value(param, value).map_err(|err| invalid(err.to_string()))?;
+
+                    values.push((param, value));
+                }
+            }
+            _ => return Err(invalid(format!("unknown key {key:?}"))),
+        }
+    }
+
+    Ok(FileStore {
+        path,
+        mode,
+        values,
+        lookup_table,
+    })
+}
+
+impl ParamStore for FileStore {
+    fn set(&mut self, param: Param, value: f64) -> crate::Result<()> {
+        validate_param_value(param, value)?;
+        self.put(param, value);
+        self.save()
+    }
+
+    fn set_unchecked(&mut self, param: Param, value: Fpt) -> crate::Result<()> {
+        self.put(param, value.into());
+        self.save()
+    }
+
+    /// The parameter's default if the file doesn't set it,
+    /// e.g. when it was created before the parameter existed, or trimmed by hand.
+    fn get(&self, param: &Param) -> crate::Result<Fpt> {
+        let value = self
+            .values
+            .iter()
+            .find(|(p, _)| p == param)
+            .map_or(param.info().default, |&(_, value)| value);
+        Ok(value.into())
+    }
+
+    /// Everything is validated, then written to the file at once,
+    /// so there's nothing to roll back but the in-memory values if that write fails.
+    fn apply(&mut self, transaction: &Transaction) -> crate::Result<Validation> {
+        let validation = transaction.check(self)?;
+        self.record(transaction)?;
+        Ok(validation)
+    }
+
+    fn set_current_accel_mode(&mut self, mode: AccelMode) -> crate::Result<()> {
+        let previous_mode = self.mode;
+        self.mode = mode;
+        self.save().inspect_err(|_| self.mode = previous_mode)
+    }
+
+    fn get_current_accel_mode(&self) -> crate::Result<AccelMode> {
+        Ok(self.mode)
+    }
+
+    fn set_lookup_table(&mut self, table: &LookupTable) -> crate::Result<()> {
+        let previous_table = self.lookup_table.clone();
+        self.lookup_table = table.clone();
+        self.save()
+            .inspect_err(|_| self.lookup_table = previous_table)
+    }
+
+    /// Without points, if none were ever set, like the driver starts with.
+    fn get_lookup_table(&self) -> crate::Result<LookupTable> {
+        Ok(self.lookup_table.clone())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn file_store_round_trips_params_and_mode() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file-store.toml");
+
+    let mut store = FileStore::open(&path).unwrap();
+    store.set(Param::SensMult, 1.5).unwrap();
+    store.set(Param::AccelClassic, 0.3).unwrap();
+    store.set_current_accel_mode(AccelMode::Classic).unwrap();
+    assert!(store.set(Param::Limit, 0.5).is_err());
+    assert!(store.get_lookup_table().unwrap().is_empty());
+    let table = LookupTable::new(&[(0.0, 1.0), (8.0, 1.5)]).unwrap();
+    store.set_lookup_table(&table).unwrap();
+
+    let store = FileStore::open(&path).unwrap();
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Classic);
+    assert_eq!(store.get_lookup_table().unwrap(), table);
+    assert_eq!(store.get(&Param::SensMult).unwrap(), Fpt::from(1.5));
+    assert_eq!(store.get(&Param::AccelClassic).unwrap(), Fpt::from(0.3));
+    assert_eq!(
+        store.get(&Param::Limit).unwrap(),
+        Fpt::from(Param::Limit.info().default)
+    );
+}
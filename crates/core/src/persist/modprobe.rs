@@ -0,0 +1,121 @@
+//! Persist the parameters as the module's load options in `/etc/modprobe.d/`,
+//! e.g. `options maccel SENS_MULT=4294967296 MODE=0`,
+//! so the driver starts off with them rather than the default curve.
+
+use std::{fs, path::Path};
+
+use crate::{
+    ALL_PARAMS, AccelMode, Error, LookupTable, Param,
+    fixedptc::{FIXEDPT_BITS, Fpt},
+};
+
+use super::ParamStore;
+
+pub const MODPROBE_OPTIONS_PATH: &str = "/etc/modprobe.d/maccel.conf";
+
+/// Render the `options` line for the module loaded as `module_name`, with every curve's
+/// parameters and `mode`, encoded exactly as they are written to `/sys/module/<module_name>/parameters`.
+/// The look-up table's points are left out when there are none.
+pub fn modprobe_options(
+    module_name: &str,
+    mode: AccelMode,
+    values: &[(Param, f64)],
+    table: &LookupTable,
+) -> crate::Result<String> {
+    let mut options = format!("options {module_name}");
+
+    for &(param, value) in values {
+        let value = encode(param, value)?;
+        options.push_str(&format!(" {}={}", param.name(), value));
+    }
+
+    if !table.is_empty() {
+        options.push_str(&format!(
+            " {}={}",
+            LookupTable::PARAM_NAME,
+            table.to_driver_string()
+        ));
+    }
+
+    options.push_str(&format!(" {}={}", AccelMode::PARAM_NAME, mode.ordinal()));
+
+    Ok(options)
+}
+
+/// Write the options file for the module loaded as `module_name`,
+/// with the current parameters and mode in `store`.
+/// Nothing keeps it up to date after that, it has to be written again.
+pub fn write_modprobe_options(
+    path: &Path,
+    module_name: &str,
+    store: &impl ParamStore,
+) -> crate::Result<()> {
+    let values = ALL_PARAMS
+        .iter()
+        .map(|&param| Ok((param, store.get(&param)?.into())))
+        .collect::<crate::Result<Vec<_>>>()?;
+    let options = modprobe_options(
+        module_name,
+        store.get_current_accel_mode()?,
+        &values,
+        &store.get_lookup_table()?,
+    )?;
+
+    fs::write(
+        path,
+        format!(
+            "# Generated by `maccel modprobe write`, changes will be overwritten.\n\
+             # Later changes to the parameters aren't in it until it's written again.\n{options}\n"
+        ),
+    )
+    .map_err(|err| Error::io(path, err))
+}
+
+fn encode(param: Param, value: f64) -> crate::Result<i64> {
+    let fpt = Fpt::checked_from(value).ok_or_else(|| {
+        Error::invalid_value(
+            param,
+            format!("{value} cannot be represented as a {FIXEDPT_BITS}-bit fixed-point number"),
+        )
+    })?;
+
+    Ok(fpt.0)
+}
+
+#[cfg(test)]
+#[test]
+fn modprobe_options_keep_every_curves_params() {
+    let values: Vec<_> = ALL_PARAMS
+        .iter()
+        .map(|&p| match p {
+            Param::AccelClassic => (p, 0.5),
+            Param::AccelLinear => (p, 0.25),
+            _ => (p, 1.0),
+        })
+        .collect();
+    let table = LookupTable::default();
+
+    let one = Fpt::from(1.0).0;
+    let options = modprobe_options("maccel2", AccelMode::Classic, &values, &table).unwrap();
+
+    assert!(options.starts_with(&format!(
+        "options maccel2 SENS_MULT={one} YX_RATIO={one} INPUT_DPI={one} LINEAR_ACCEL={} ",
+        Fpt::from(0.25).0
+    )));
+    assert!(options.contains(&format!(" CLASSIC_ACCEL={} POWER={one} ", Fpt::from(0.5).0)));
+    assert!(options.contains(&format!(" NATURAL_OFFSET={one} ")));
+    assert!(options.contains(&format!(" SYNC_SPEED={one} ")));
+    assert!(options.contains(&format!(" JUMP_SMOOTH={one} ")));
+    assert!(options.contains(&format!(" POWER_OUTPUT_CAP={one} ")));
+    assert!(options.contains(&format!(" WINDOWS_SCREEN_DPI={one} ")));
+    assert!(options.ends_with(&format!(" MACOS_TRACKING_SPEED={one} MODE=1")));
+
+    let too_big = [(Param::SensMult, 1e30)];
+    assert!(matches!(
+        modprobe_options("maccel", AccelMode::Linear, &too_big, &table),
+        Err(Error::InvalidValue {
+            param: Param::SensMult,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,148 @@
+//! Named snapshots of all the parameters and the acceleration mode,
+//! each saved as a [`FileStore`] file in a profiles directory.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::Error;
+
+use super::{FileStore, ParamStore, Transaction};
+
+const PROFILE_EXTENSION: &str = "toml";
+
+#[derive(Debug)]
+pub struct Profiles {
+    dir: PathBuf,
+}
+
+impl Profiles {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$XDG_CONFIG_HOME/maccel/profiles`, or `~/.config/maccel/profiles`
+    pub fn default_dir() -> crate::Result<PathBuf> {
+        let config_dir = match env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => env::var_os("HOME")
+                .map(|home| PathBuf::from(home).join(".config"))
+                .ok_or(Error::ConfigDirNotFound)?,
+        };
+
+        Ok(config_dir.join("maccel").join("profiles"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The names of all the saved profiles, sorted.
+    pub fn list(&self) -> crate::Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut names = vec![];
+        let read_error = |err| Error::io(&self.dir, err);
+        for entry in fs::read_dir(&self.dir).map_err(read_error)? {
+            let path = entry.map_err(read_error)?.path();
+            if path.extension().is_some_and(|ext| ext == PROFILE_EXTENSION)
+                && let Some(name) = path.file_stem().and_then(|n| n.to_str())
+            {
+                names.push(name.to_string());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Open a saved profile, to read or edit it like any other [`ParamStore`].
+    pub fn open(&self, name: &str) -> crate::Result<FileStore> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Err(Error::UnknownProfile(name.to_string()));
+        }
+
+        FileStore::open(path)
+    }
+
+    /// Snapshot every parameter and the mode from `store` into the profile `name`,
+    /// overwriting it if it exists.
+    pub fn save(&self, name: &str, store: &impl ParamStore) -> crate::Result<()> {
+        let args = store.get_all()?;
+
+        let mut profile = FileStore::open(self.path(name)?)?;
+        profile.apply(&Transaction::from(&args).mode(store.get_current_accel_mode()?))?;
+        Ok(())
+    }
+
+    /// Apply every parameter and the mode saved in the profile `name` to `store`.
+    pub fn load(&self, name: &str, store: &mut impl ParamStore) -> crate::Result<()> {
+        let profile = self.open(name)?;
+        let args = profile.get_all()?;
+
+        store.apply(&Transaction::from(&args).mode(profile.get_current_accel_mode()?))?;
+        Ok(())
+    }
+
+    pub fn delete(&self, name: &str) -> crate::Result<()> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Err(Error::UnknownProfile(name.to_string()));
+        }
+
+        fs::remove_file(&path).map_err(|err| Error::io(&path, err))
+    }
+
+    fn path(&self, name: &str) -> crate::Result<PathBuf> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(Error::InvalidProfileName(name.to_string()));
+        }
+
+        Ok(self.dir.join(format!("{name}.{PROFILE_EXTENSION}")))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn profiles_save_list_load_and_delete() {
+    use crate::{AccelMode, Param, fixedptc::Fpt};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let profiles = Profiles::new(dir);
+
+    let mut live = FileStore::open(dir.join("live.state")).unwrap();
+    for &param in crate::ALL_PARAMS {
+        let value = match param {
+            Param::Smooth | Param::SmoothJump => 0.5,
+            _ => 2.0,
+        };
+        live.set(param, value).unwrap();
+    }
+    live.set_current_accel_mode(AccelMode::Natural).unwrap();
+
+    profiles.save("game", &live).unwrap();
+    assert_eq!(profiles.list().unwrap(), vec!["game".to_string()]);
+    assert!(matches!(
+        profiles.save("../escape", &live),
+        Err(Error::InvalidProfileName(_))
+    ));
+
+    live.set(Param::SensMult, 1.0).unwrap();
+    live.set_current_accel_mode(AccelMode::Linear).unwrap();
+
+    profiles.load("game", &mut live).unwrap();
+    assert_eq!(live.get(&Param::SensMult).unwrap(), Fpt::from(2.0));
+    assert_eq!(live.get_current_accel_mode().unwrap(), AccelMode::Natural);
+
+    profiles.delete("game").unwrap();
+    assert!(profiles.list().unwrap().is_empty());
+    assert!(matches!(
+        profiles.load("game", &mut live),
+        Err(Error::UnknownProfile(_))
+    ));
+}
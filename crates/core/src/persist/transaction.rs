@@ -0,0 +1,320 @@
+//! A set of parameter values, and maybe a mode, to apply to a [`ParamStore`] all at once,
+//! so that it never ends up with a half-applied curve.
+
+use crate::{
+    ALL_MODES, ALL_PARAMS, AccelMode, AllParamArgs, Error, LookupTable, Param,
+    fixedptc::Fpt,
+    params::validate::validate_param_value,
+    validation::{Validation, validate_params},
+};
+
+use super::ParamStore;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Transaction {
+    values: Vec<(Param, f64)>,
+    lookup_table: Option<LookupTable>,
+    mode: Option<AccelMode>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set each of `params` back to the value the driver starts with, see [`ParamInfo`].
+    ///
+    /// [`ParamInfo`]: crate::ParamInfo
+    pub fn defaults(params: &[Param]) -> Self {
+        params.iter().fold(Self::new(), |tx, &param| {
+            tx.set(param, param.info().default)
+        })
+    }
+
+    /// Set `param` to `value`, replacing any value already in the transaction for it.
+    pub fn set(mut self, param: Param, value: f64) -> Self {
+        match self.values.iter_mut().find(|(p, _)| *p == param) {
+            Some((_, v)) => *v = value,
+            None => self.values.push((param, value)),
+        }
+        self
+    }
+
+    /// Replace the points of the look-up table, once the values are applied.
+    pub fn lookup_table(mut self, table: LookupTable) -> Self {
+        self.lookup_table = Some(table);
+        self
+    }
+
+    /// Switch to `mode` once all the values are applied.
+    pub fn mode(mut self, mode: AccelMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn values(&self) -> &[(Param, f64)] {
+        &self.values
+    }
+
+    pub fn points(&self) -> Option<&LookupTable> {
+        self.lookup_table.as_ref()
+    }
+
+    pub fn accel_mode(&self) -> Option<AccelMode> {
+        self.mode
+    }
+
+    /// Check every value, before any of them gets applied.
+    pub fn validate(&self) -> crate::Result<()> {
+        for &(param, value) in &self.values {
+            validate_param_value(param, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Every parameter's value, and the mode, as they would be in `store` after applying
+    /// the transaction. The ones `store` doesn't have, like a module older than the parameter,
+    /// are taken to be their defaults, see [`ParamInfo`].
+    ///
+    /// [`ParamInfo`]: crate::ParamInfo
+    pub fn preview(
+        &self,
+        store: &(impl ParamStore + ?Sized),
+    ) -> crate::Result<(AccelMode, AllParamArgs)> {
+        fn or_default<T>(value: crate::Result<T>, default: impl FnOnce() -> T) -> crate::Result<T> {
+            match value {
+                Err(Error::UnsupportedParameter { .. }) => Ok(default()),
+                value => value,
+            }
+        }
+
+        let mut args = AllParamArgs::try_from_fn(|param| {
+            match self.values.iter().find(|(p, _)| *p == param) {
+                Some(&(_, value)) => Ok(value.into()),
+                None => or_default(store.get(&param), || param.info().default.into()),
+            }
+        })?;
+        args.lookup_table.points = match &self.lookup_table {
+            Some(table) => table.clone(),
+            None => or_default(store.get_lookup_table(), LookupTable::default)?,
+        };
+
+        let mode = match self.mode {
+            Some(mode) => mode,
+            None => store.get_current_accel_mode()?,
+        };
+
+        Ok((mode, args))
+    }
+
+    /// Check every value, then the parameters as they would be in `store` together,
+    /// see [`validate_params`], for the mode and any other curve the transaction changes.
+    /// Fails on any error, and gives back the warnings.
+    pub fn check(&self, store: &(impl ParamStore + ?Sized)) -> crate::Result<Validation> {
+        self.validate()?;
+
+        let (mode, args) = self.preview(store)?;
+
+        let mut validation = validate_params(mode, &args);
+        for other in ALL_MODES.iter().filter(|&&m| m != mode) {
+            if self.values.iter().any(|(p, _)| other.params().contains(p)) {
+                let other_validation = validate_params(*other, &args);
+                for issue in other_validation.issues {
+                    if !validation.issues.contains(&issue) {
+                        validation.issues.push(issue);
+                    }
+                }
+            }
+        }
+
+        validation.into_result()
+    }
+
+    /// Validate and apply the transaction to `store`, one value at a time, then the look-up table,
+    /// restoring the values it had before if anything fails, as they were, without validating them.
+    /// The mode is switched last, in one write, so it's left alone when anything fails.
+    /// If restoring some of them fails too, that's a [`Error::RollbackFailed`].
+    ///
+    /// Gives back the warnings, see [`Transaction::check`].
+    ///
+    /// This is what [`ParamStore::apply`] does unless a store has a better way to do it.
+    pub fn apply_to(&self, store: &mut (impl ParamStore + ?Sized)) -> crate::Result<Validation> {
+        let validation = self.check(store)?;
+
+        let snapshot = self
+            .values
+            .iter()
+            .map(|&(param, _)| Ok((param, store.get(&param)?)))
+            .collect::<crate::Result<Vec<(Param, Fpt)>>>()?;
+        let previous_table = match self.lookup_table {
+            Some(_) => Some(store.get_lookup_table()?),
+            None => None,
+        };
+
+        let result = self
+            .values
+            .iter()
+            .try_for_each(|&(param, value)| store.set(param, value))
+            .and_then(|_| match &self.lookup_table {
+                Some(table) => store.set_lookup_table(table),
+                None => Ok(()),
+            })
+            .and_then(|_| match self.mode {
+                Some(mode) => store.set_current_accel_mode(mode),
+                None => Ok(()),
+            });
+
+        let Err(err) = result else {
+            return Ok(validation);
+        };
+
+        let mut failures = vec![];
+        for (param, value) in snapshot {
+            if let Err(err) = store.set_unchecked(param, value) {
+                failures.push((param.name(), err));
+            }
+        }
+        if let Some(table) = previous_table
+            && let Err(err) = store.set_lookup_table(&table)
+        {
+            failures.push((LookupTable::PARAM_NAME, err));
+        }
+
+        if !failures.is_empty() {
+            return Err(Error::RollbackFailed {
+                source: Box::new(err),
+                failures,
+            });
+        }
+
+        Err(err)
+    }
+}
+
+impl From<&AllParamArgs> for Transaction {
+    fn from(args: &AllParamArgs) -> Self {
+        ALL_PARAMS
+            .iter()
+            .fold(Self::new(), |tx, &param| {
+                tx.set(param, args.get(param).into())
+            })
+            .lookup_table(args.lookup_table.points.clone())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn defaults_are_the_driver_defaults() {
+    let tx = Transaction::defaults(crate::ALL_NATURAL_PARAMS);
+
+    assert_eq!(
+        tx.values(),
+        [
+            (Param::DecayRate, 0.1),
+            (Param::OffsetNatural, 0.0),
+            (Param::Limit, 1.5)
+        ]
+    );
+    assert_eq!(tx.accel_mode(), None);
+    tx.validate().unwrap();
+}
+
+#[cfg(test)]
+#[test]
+fn transaction_rolls_back_on_failure() {
+    #[derive(Debug)]
+    struct FlakyStore {
+        values: Vec<(Param, f64)>,
+        mode: AccelMode,
+        fail_on: Param,
+    }
+
+    impl ParamStore for FlakyStore {
+        fn set(&mut self, param: Param, value: f64) -> crate::Result<()> {
+            if param == self.fail_on {
+                return Err(Error::PermissionDenied {
+                    path: param.name().into(),
+                });
+            }
+            self.set_unchecked(param, value.into())
+        }
+
+        fn set_unchecked(&mut self, param: Param, value: Fpt) -> crate::Result<()> {
+            let value = value.into();
+            match self.values.iter_mut().find(|(p, _)| *p == param) {
+                Some((_, v)) => *v = value,
+                None => self.values.push((param, value)),
+            }
+            Ok(())
+        }
+
+        fn get(&self, param: &Param) -> crate::Result<Fpt> {
+            Ok(self
+                .values
+                .iter()
+                .find(|(p, _)| p == param)
+                .map_or(1.0, |&(_, v)| v)
+                .into())
+        }
+
+        fn set_current_accel_mode(&mut self, mode: AccelMode) -> crate::Result<()> {
+            self.mode = mode;
+            Ok(())
+        }
+
+        fn get_current_accel_mode(&self) -> crate::Result<AccelMode> {
+            Ok(self.mode)
+        }
+
+        fn set_lookup_table(&mut self, _: &LookupTable) -> crate::Result<()> {
+            Ok(())
+        }
+
+        fn get_lookup_table(&self) -> crate::Result<LookupTable> {
+            Ok(LookupTable::default())
+        }
+    }
+
+    // A value we'd reject now, e.g. from before its bounds were tightened.
+    let mut store = FlakyStore {
+        values: vec![(Param::Limit, 0.5)],
+        mode: AccelMode::Linear,
+        fail_on: Param::OutputCapClassic,
+    };
+
+    let tx = Transaction::new()
+        .set(Param::Limit, 2.0)
+        .set(Param::AccelClassic, 0.3)
+        .set(Param::PowerClassic, 3.0)
+        .set(Param::OutputCapClassic, 2.0)
+        .mode(AccelMode::Classic);
+
+    // Every value is put back as it was, without going through the failing, validating setter.
+    assert!(matches!(
+        store.apply(&tx),
+        Err(Error::PermissionDenied { .. })
+    ));
+    assert_eq!(store.get(&Param::Limit).unwrap(), Fpt::from(0.5));
+    assert_eq!(store.get(&Param::AccelClassic).unwrap(), Fpt::from(1.0));
+    assert_eq!(store.get(&Param::PowerClassic).unwrap(), Fpt::from(1.0));
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Linear);
+
+    let before = store.values.clone();
+    let invalid = Transaction::new()
+        .set(Param::AccelClassic, 0.3)
+        .set(Param::Limit, 0.5);
+    assert!(matches!(
+        store.apply(&invalid),
+        Err(Error::InvalidValue {
+            param: Param::Limit,
+            ..
+        })
+    ));
+    assert_eq!(store.values, before);
+
+    store.fail_on = Param::Gamma;
+    store.apply(&tx).unwrap();
+    assert_eq!(store.get(&Param::OutputCapClassic).unwrap(), Fpt::from(2.0));
+    assert_eq!(store.get_current_accel_mode().unwrap(), AccelMode::Classic);
+}
@@ -0,0 +1,133 @@
+//! How far the driver's fixed-point sensitivity is from the curves as they are defined,
+//! computed with floating-point numbers, see [`reference_sensitivity`].
+
+use crate::{
+    AccelMode, AllParamArgs, fixedptc::FixedptWidth, reference_sensitivity, sensitivity_for_width,
+};
+
+/// How many input speeds to compare the sensitivity at.
+pub const SAMPLES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionReport {
+    pub width: FixedptWidth,
+    /// The largest difference with the reference sensitivity, on either axis.
+    pub max_error: f64,
+    /// The input speed, in counts/ms, with the largest difference.
+    pub max_error_at: f64,
+    pub mean_error: f64,
+    /// How many input speeds the sensitivity was compared at,
+    /// leaving out those where the reference curve isn't a finite number.
+    pub samples: usize,
+}
+
+/// Compare the sensitivity the driver computes when built for `width`, with the reference,
+/// at input speeds evenly spread up to `max_speed` counts/ms.
+pub fn precision_report(
+    mode: AccelMode,
+    params: &AllParamArgs,
+    width: FixedptWidth,
+    max_speed: f64,
+) -> PrecisionReport {
+    let mut report = PrecisionReport {
+        width,
+        max_error: 0.0,
+        max_error_at: 0.0,
+        mean_error: 0.0,
+        samples: 0,
+    };
+    let mut total_error = 0.0;
+
+    // Starting past 0, where there's no movement to accelerate.
+    for speed in (1..=SAMPLES).map(|i| i as f64 * max_speed / SAMPLES as f64) {
+        let (ref_x, ref_y) = reference_sensitivity(speed, mode, params);
+        if !ref_x.is_finite() || !ref_y.is_finite() {
+            continue;
+        }
+
+        let (x, y) = sensitivity_for_width(speed, mode, params, width);
+        let error = (x - ref_x).abs().max((y - ref_y).abs());
+
+        if error > report.max_error || error.is_nan() {
+            report.max_error = error;
+            report.max_error_at = speed;
+        }
+        total_error += error;
+        report.samples += 1;
+    }
+
+    if report.samples > 0 {
+        report.mean_error = total_error / report.samples as f64;
+    }
+
+    report
+}
+
+#[cfg(test)]
+#[test]
+fn fixed_point_sensitivity_is_close_to_the_reference() {
+    use crate::{Param, fixedptc::Fpt};
+
+    let params = AllParamArgs::from_fn(|p| {
+        Fpt::from(match p {
+            Param::SensMult => 1.0,
+            Param::YxRatio => 1.15,
+            Param::InputDpi => 1000.0,
+            Param::AccelLinear => 0.3,
+            Param::OffsetLinear => 2.0,
+            Param::OutputCapLinear => 2.0,
+            Param::AccelClassic => 0.3,
+            Param::PowerClassic => 2.5,
+            Param::OffsetClassic => 2.0,
+            Param::OutputCapClassic => 0.0,
+            Param::DecayRate => 0.1,
+            Param::OffsetNatural => 8.0,
+            Param::Limit => 1.5,
+            Param::Gamma => 0.8,
+            Param::Smooth => 0.5,
+            Param::Motivity => 1.5,
+            Param::SyncSpeed => 32.0,
+            Param::JumpSpeed => 15.0,
+            Param::JumpSens => 1.5,
+            Param::SmoothJump => 0.5,
+            Param::ScalePower => 0.5,
+            Param::ExponentPower => 0.4,
+            Param::OutputOffsetPower => 1.0,
+            Param::OutputCapPower => 0.0,
+            Param::SliderWindows => 8.0,
+            Param::RefreshRateWindows => 144.0,
+            Param::ScreenDpiWindows => 120.0,
+            Param::TrackingSpeed => 3.0,
+        })
+    });
+
+    for mode in [
+        AccelMode::Linear,
+        AccelMode::Classic,
+        AccelMode::Natural,
+        AccelMode::Synchronous,
+        AccelMode::Jump,
+        AccelMode::Power,
+        AccelMode::Windows,
+        AccelMode::Macos,
+    ] {
+        let report_32 = precision_report(mode, &params, FixedptWidth::W32, 128.0);
+        let report_64 = precision_report(mode, &params, FixedptWidth::W64, 128.0);
+
+        assert_eq!(report_64.samples, SAMPLES, "{mode:?}");
+        assert!(report_64.mean_error < 1e-3, "{mode:?}: {report_64:?}");
+        assert!(report_64.mean_error <= report_32.mean_error, "{mode:?}");
+        assert!(report_64.max_error < 1e-4, "{mode:?}: {report_64:?}");
+        assert!(report_32.max_error < 1e-1, "{mode:?}: {report_32:?}");
+    }
+
+    // Below the offset, and past the output cap.
+    assert_eq!(
+        reference_sensitivity(1.0, AccelMode::Linear, &params).0,
+        1.0
+    );
+    assert_eq!(
+        reference_sensitivity(12.0, AccelMode::Linear, &params).0,
+        2.0
+    );
+}
@@ -1,13 +1,18 @@
 use crate::{
-    libmaccel::{self, fixedptc::Fpt},
+    AccelParams, AccelParamsByMode, ClassicCurveParams, JumpCurveParams, LinearCurveParams,
+    MacosCurveParams, NaturalCurveParams, Param, PowerCurveParams, SynchronousCurveParams,
+    WindowsCurveParams,
+    libmaccel::{
+        self,
+        fixedptc::{FIXEDPT_BITS, FIXEDPT_WBITS, FixedptWidth, Fpt},
+    },
     params::AllParamArgs,
-    AccelParams, AccelParamsByMode, ClassicCurveParams, LinearCurveParams, NaturalCurveParams, SynchronousCurveParams
 };
 
 use crate::AccelMode;
 
 impl AllParamArgs {
-    fn convert_to_accel_args(&self, mode: AccelMode) -> AccelParams {
+    pub(crate) fn convert_to_accel_args(&self, mode: AccelMode) -> AccelParams {
         let params_by_mode = match mode {
             AccelMode::Linear => AccelParamsByMode::Linear(LinearCurveParams {
                 accel_linear: self.accel_linear,
@@ -31,6 +36,28 @@ impl AllParamArgs {
                 motivity: self.motivity,
                 sync_speed: self.sync_speed,
             }),
+            AccelMode::Jump => AccelParamsByMode::Jump(JumpCurveParams {
+                jump_speed: self.jump_speed,
+                jump_sens: self.jump_sens,
+                smooth_jump: self.smooth_jump,
+            }),
+            AccelMode::Power => AccelParamsByMode::Power(PowerCurveParams {
+                scale_power: self.scale_power,
+                exponent_power: self.exponent_power,
+                output_offset_power: self.output_offset_power,
+                output_cap_power: self.output_cap_power,
+            }),
+            AccelMode::Windows => AccelParamsByMode::Windows(WindowsCurveParams {
+                slider_windows: self.slider_windows,
+                refresh_rate_windows: self.refresh_rate_windows,
+                screen_dpi_windows: self.screen_dpi_windows,
+            }),
+            AccelMode::Macos => AccelParamsByMode::Macos(MacosCurveParams {
+                tracking_speed: self.tracking_speed,
+            }),
+            AccelMode::LookupTable => {
+                AccelParamsByMode::LookupTable(Box::new((&self.lookup_table.points).into()))
+            }
         };
 
         AccelParams {
@@ -46,10 +73,399 @@ pub type SensXY = (f64, f64);
 
 /// Ratio of Output speed to Input speed
 pub fn sensitivity(s_in: f64, mode: AccelMode, params: &AllParamArgs) -> SensXY {
-    let sens =
-        unsafe { libmaccel::sensitivity_rs(s_in.into(), params.convert_to_accel_args(mode)) };
+    accel_params_sensitivity(s_in, &params.convert_to_accel_args(mode))
+}
+
+/// [`sensitivity`], with the parameters already as the driver takes them.
+pub(crate) fn accel_params_sensitivity(s_in: f64, params: &AccelParams) -> SensXY {
+    let sens = unsafe { libmaccel::sensitivity_rs(s_in.into(), params) };
     let ratio_x: f64 = Fpt(sens.x).into();
     let ratio_y: f64 = Fpt(sens.y).into();
 
     (ratio_x, ratio_y)
 }
+
+/// [`sensitivity`] as the driver computes it when it's built for `width`,
+/// which might not be the width this crate is built for.
+pub fn sensitivity_for_width(
+    s_in: f64,
+    mode: AccelMode,
+    params: &AllParamArgs,
+    width: FixedptWidth,
+) -> SensXY {
+    if width == FixedptWidth::CURRENT {
+        return sensitivity(s_in, mode, params);
+    }
+
+    libmaccel::sensitivity_for_width(width, s_in, mode, params)
+}
+
+/// Speed out, in counts/ms, for a speed in: the input speed scaled by its [`sensitivity`].
+pub fn output_speed(s_in: f64, mode: AccelMode, params: &AllParamArgs) -> SensXY {
+    let s_in = clamp_to_fixedpt(s_in);
+    let (sens_x, sens_y) = sensitivity(s_in, mode, params);
+
+    (s_in * sens_x, s_in * sens_y)
+}
+
+/// How fast the [`output_speed`] changes with the input speed, `d(speed_out) / d(speed_in)`.
+///
+/// It's the slope of the driver's fixed-point curve, over a step wide enough that the rounding
+/// of the sensitivity doesn't dominate. The step doesn't cross the mode's offset,
+/// where the curve has a corner; up to the offset, the sensitivity is constant,
+/// so the gain is the sensitivity.
+pub fn gain(s_in: f64, mode: AccelMode, params: &AllParamArgs) -> SensXY {
+    let s_in = clamp_to_fixedpt(s_in);
+    let offset = offset(mode, params);
+    if offset.is_some_and(|offset| s_in <= offset) {
+        return sensitivity(s_in, mode, params);
+    }
+
+    // Half the fractional bits, which balances the rounding error of the sensitivity
+    // with the error of taking the slope over a step.
+    let step = s_in.max(1.0) * 2f64.powi(-((FIXEDPT_BITS - FIXEDPT_WBITS) as i32 / 2));
+    let mut low = (s_in - step).max(0.0);
+    let high = clamp_to_fixedpt(s_in + step);
+    if offset.is_some_and(|offset| low <= offset) {
+        low = s_in;
+    }
+
+    let (low_x, low_y) = output_speed(low, mode, params);
+    let (high_x, high_y) = output_speed(high, mode, params);
+
+    (
+        (high_x - low_x) / (high - low),
+        (high_y - low_y) / (high - low),
+    )
+}
+
+/// The input speed past which the mode's curve starts to accelerate, if it has one.
+fn offset(mode: AccelMode, params: &AllParamArgs) -> Option<f64> {
+    let param = match mode {
+        AccelMode::Linear => Param::OffsetLinear,
+        AccelMode::Classic => Param::OffsetClassic,
+        AccelMode::Natural => Param::OffsetNatural,
+        AccelMode::Synchronous
+        | AccelMode::Jump
+        | AccelMode::Power
+        | AccelMode::Windows
+        | AccelMode::Macos
+        | AccelMode::LookupTable => return None,
+    };
+
+    Some(f64::from(params.get(param)))
+}
+
+/// An input speed is a magnitude, that the driver can only represent up to
+/// the largest fixed-point number.
+fn clamp_to_fixedpt(s_in: f64) -> f64 {
+    let max = (1u64 << (FIXEDPT_WBITS - 1)) as f64 - 1.0;
+    s_in.clamp(0.0, max)
+}
+
+#[cfg(test)]
+#[test]
+fn gain_is_the_slope_of_the_output_speed() {
+    let params = AllParamArgs::from_fn(|p| {
+        Fpt::from(match p {
+            Param::YxRatio => 2.0,
+            Param::AccelLinear => 0.125,
+            Param::OffsetLinear => 4.0,
+            Param::OutputCapLinear => 0.0,
+            _ => 1.0,
+        })
+    });
+
+    // The sensitivity is `1 + a(s - o)^2 / s`, so the output speed is `s + a(s - o)^2`,
+    // and the gain `1 + 2a(s - o)`.
+    let (x, y) = gain(10.0, AccelMode::Linear, &params);
+    assert!((x - 2.5).abs() < 1e-2, "{x}");
+    assert!((y - 5.0).abs() < 1e-2, "{y}");
+
+    let (x, _) = gain(4.5, AccelMode::Linear, &params);
+    assert!((x - 1.125).abs() < 1e-2, "{x}");
+
+    assert_eq!(gain(2.0, AccelMode::Linear, &params), (1.0, 2.0));
+    assert_eq!(output_speed(2.0, AccelMode::Linear, &params), (2.0, 4.0));
+    assert_eq!(output_speed(-1.0, AccelMode::Linear, &params), (0.0, 0.0));
+}
+
+/// The sensitivity as the curves are defined, computed with `f64`s,
+/// to compare the driver's fixed-point approximation against.
+pub fn reference_sensitivity(s_in: f64, mode: AccelMode, params: &AllParamArgs) -> SensXY {
+    let value = |param: Param| f64::from(params.get(param));
+
+    let sens = match mode {
+        AccelMode::Linear => reference::linear(
+            s_in,
+            value(Param::AccelLinear),
+            value(Param::OffsetLinear),
+            value(Param::OutputCapLinear),
+        ),
+        AccelMode::Classic => reference::classic(
+            s_in,
+            value(Param::AccelClassic),
+            value(Param::PowerClassic),
+            value(Param::OffsetClassic),
+            value(Param::OutputCapClassic),
+        ),
+        AccelMode::Natural => reference::natural(
+            s_in,
+            value(Param::DecayRate),
+            value(Param::OffsetNatural),
+            value(Param::Limit),
+        ),
+        AccelMode::Synchronous => reference::synchronous(
+            s_in,
+            value(Param::Gamma),
+            value(Param::Smooth),
+            value(Param::Motivity),
+            value(Param::SyncSpeed),
+        ),
+        AccelMode::Jump => reference::jump(
+            s_in,
+            value(Param::JumpSpeed),
+            value(Param::JumpSens),
+            value(Param::SmoothJump),
+        ),
+        AccelMode::Power => reference::power(
+            s_in,
+            value(Param::ScalePower),
+            value(Param::ExponentPower),
+            value(Param::OutputOffsetPower),
+            value(Param::OutputCapPower),
+        ),
+        AccelMode::Windows => reference::windows(
+            s_in,
+            value(Param::InputDpi),
+            value(Param::SliderWindows),
+            value(Param::RefreshRateWindows),
+            value(Param::ScreenDpiWindows),
+        ),
+        AccelMode::Macos => reference::macos(s_in, value(Param::TrackingSpeed)),
+        AccelMode::LookupTable => {
+            reference::lookup_table(s_in, params.lookup_table.points.points())
+        }
+    };
+    let sens = sens * value(Param::SensMult);
+
+    (sens, sens * value(Param::YxRatio))
+}
+
+/// The sensitivity functions of the driver's curves, in `driver/accel/`, with `f64`s.
+pub mod reference {
+    pub fn linear(input_speed: f64, accel: f64, offset: f64, output_cap: f64) -> f64 {
+        if input_speed <= offset {
+            return 1.0;
+        }
+
+        let x = input_speed - offset;
+        with_output_cap(accel * x * x / input_speed, output_cap)
+    }
+
+    pub fn classic(input_speed: f64, accel: f64, power: f64, offset: f64, output_cap: f64) -> f64 {
+        if input_speed <= offset {
+            return 1.0;
+        }
+
+        let sens = (accel * (input_speed - offset)).powf(power) / input_speed;
+        with_output_cap(sens, output_cap)
+    }
+
+    pub fn natural(input_speed: f64, decay_rate: f64, offset: f64, limit: f64) -> f64 {
+        if input_speed <= offset || limit <= 1.0 || decay_rate <= 0.0 {
+            return 1.0;
+        }
+
+        let limit = limit - 1.0;
+        let accel = decay_rate / limit.abs();
+        let constant = -limit / accel;
+
+        let offset_x = offset - input_speed;
+        let decay = (accel * offset_x).exp();
+        let output = limit * (decay / accel - offset_x) + constant;
+
+        output / input_speed + 1.0
+    }
+
+    pub fn synchronous(
+        input_speed: f64,
+        gamma: f64,
+        smooth: f64,
+        motivity: f64,
+        sync_speed: f64,
+    ) -> f64 {
+        let log_motivity = motivity.ln();
+        let gamma_const = gamma / log_motivity;
+        let log_diff = input_speed.ln() - sync_speed.ln();
+        let sharpness = if smooth == 0.0 { 16.0 } else { 0.5 / smooth };
+
+        // The driver clamps linearly when it's this sharp.
+        if sharpness >= 16.0 {
+            let log_space = (gamma_const * log_diff).clamp(-1.0, 1.0);
+            return (log_space * log_motivity).exp();
+        }
+
+        if input_speed == sync_speed {
+            return 1.0;
+        }
+
+        let log_space = gamma_const * log_diff.abs();
+        let exponent = log_space.powf(sharpness).tanh().powf(sharpness.recip()) * log_diff.signum();
+        (exponent * log_motivity).exp()
+    }
+
+    pub fn jump(input_speed: f64, speed: f64, sens: f64, smooth: f64) -> f64 {
+        let width = smooth * speed;
+        // The driver jumps sharply when the transition is narrower than this.
+        if width < 1.0 {
+            return if input_speed < speed { 1.0 } else { sens };
+        }
+
+        let rate = std::f64::consts::TAU / width;
+        1.0 + (sens - 1.0) / (1.0 + (-rate * (input_speed - speed)).exp())
+    }
+
+    pub fn power(
+        input_speed: f64,
+        scale: f64,
+        exponent: f64,
+        output_offset: f64,
+        output_cap: f64,
+    ) -> f64 {
+        let base = scale * input_speed;
+        let sens = if base > 0.0 {
+            output_offset + base.powf(exponent)
+        } else {
+            output_offset
+        };
+
+        if output_cap > 0.0 {
+            sens.min(output_cap)
+        } else {
+            sens
+        }
+    }
+
+    /// Windows' default SmoothMouseXCurve and SmoothMouseYCurve, as `(mouse speed, pointer speed)`,
+    /// both in in/s, see `driver/accel/windows.h`.
+    pub const WINDOWS_CURVE: [(f64, f64); 5] = [
+        (0.0, 0.0),
+        (0.43, 1.37),
+        (1.25, 5.30),
+        (3.86, 24.30),
+        (40.0, 568.0),
+    ];
+
+    /// The multipliers of the pointer speed slider's notches, from 1 to 11,
+    /// with "Enhance pointer precision" on.
+    pub const WINDOWS_SLIDER_MULTIPLIERS: [f64; 11] =
+        [0.1, 0.2, 0.4, 0.6, 0.8, 1.0, 1.2, 1.4, 1.6, 1.8, 2.0];
+
+    /// Windows takes the counts of any mouse for those of a 400 DPI one.
+    pub fn windows(
+        input_speed: f64,
+        input_dpi: f64,
+        slider: f64,
+        refresh_rate: f64,
+        screen_dpi: f64,
+    ) -> f64 {
+        let mouse_speed = input_speed * input_dpi / 400.0 * 60.0 / refresh_rate;
+
+        let segment = WINDOWS_CURVE
+            .windows(2)
+            .find(|pair| mouse_speed <= pair[1].0)
+            .unwrap_or(&WINDOWS_CURVE[WINDOWS_CURVE.len() - 2..]);
+        let [(mouse0, pointer0), (mouse1, pointer1)] = [segment[0], segment[1]];
+        let slope = (pointer1 - pointer0) / (mouse1 - mouse0);
+
+        // The first segment goes through the origin, where the gain is its slope.
+        let gain = if mouse0 == 0.0 {
+            slope
+        } else {
+            (pointer0 + slope * (mouse_speed - mouse0)) / mouse_speed
+        };
+
+        let notch = slider
+            .round()
+            .clamp(1.0, WINDOWS_SLIDER_MULTIPLIERS.len() as f64);
+        let multiplier = WINDOWS_SLIDER_MULTIPLIERS[notch as usize - 1];
+
+        gain * multiplier * screen_dpi / 400.0 * input_dpi / 1000.0
+    }
+
+    pub fn macos(input_speed: f64, tracking_speed: f64) -> f64 {
+        let u = input_speed / 8.0;
+        1.0 + tracking_speed * u * u / (1.0 + u * u)
+    }
+
+    pub fn lookup_table(input_speed: f64, points: &[(f64, f64)]) -> f64 {
+        let Some(&(first_speed, first_sens)) = points.first() else {
+            return 1.0;
+        };
+        if input_speed <= first_speed {
+            return first_sens;
+        }
+
+        for pair in points.windows(2) {
+            let [(speed0, sens0), (speed1, sens1)] = [pair[0], pair[1]];
+            if input_speed <= speed1 {
+                let t = (input_speed - speed0) / (speed1 - speed0);
+                return sens0 + t * (sens1 - sens0);
+            }
+        }
+
+        points.last().map_or(1.0, |&(_, sens)| sens)
+    }
+
+    /// Cap the acceleration, so the sensitivity doesn't go past `output_cap`, or 0 for no cap.
+    fn with_output_cap(sens: f64, output_cap: f64) -> f64 {
+        if output_cap <= 0.0 {
+            return 1.0 + sens;
+        }
+
+        let cap = output_cap - 1.0;
+        1.0 + cap.signum() * sens.min(cap.abs())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn windows_moves_the_pointer_as_windows_does() {
+    let params = |input_dpi: f64, slider: f64| {
+        AllParamArgs::from_fn(|p| {
+            Fpt::from(match p {
+                Param::InputDpi => input_dpi,
+                Param::SliderWindows => slider,
+                _ => p.info().default,
+            })
+        })
+    };
+    let sens = |s_in: f64, params: &AllParamArgs| {
+        let (fixed, _) = sensitivity(s_in, AccelMode::Windows, params);
+        let (reference, _) = reference_sensitivity(s_in, AccelMode::Windows, params);
+        assert!((fixed - reference).abs() < 1e-3, "{fixed} != {reference}");
+        fixed
+    };
+    let pixels_per_count = |s_in: f64, input_dpi: f64, slider: f64| {
+        sens(s_in, &params(input_dpi, slider)) * 1000.0 / input_dpi
+    };
+
+    // Slow, on the first segment, a count moves the pointer 1.37 / 0.43 * 96 / 400 pixels,
+    // whatever the mouse's DPI.
+    let slow = 1.37 / 0.43 * 96.0 / 400.0;
+    assert!((pixels_per_count(0.1, 1000.0, 6.0) - slow).abs() < 1e-3);
+    assert!((pixels_per_count(0.04, 400.0, 6.0) - slow).abs() < 1e-3);
+
+    // Windows takes a 1000 DPI mouse at 0.5 counts/ms to be moving at 1.25 in/s,
+    // which the curve moves the pointer at 5.30 in/s for.
+    assert!((pixels_per_count(0.5, 1000.0, 6.0) - 5.30 / 1.25 * 96.0 / 400.0).abs() < 1e-3);
+
+    // The notches past the 6th add 0.2 each, and the slider is rounded to the nearest one.
+    assert!((pixels_per_count(0.1, 1000.0, 11.0) - 2.0 * slow).abs() < 1e-3);
+    assert!((pixels_per_count(0.1, 1000.0, 1.4) - 0.1 * slow).abs() < 1e-3);
+    assert_eq!(
+        sens(0.1, &params(1000.0, 7.6)),
+        sens(0.1, &params(1000.0, 8.0))
+    );
+}
@@ -0,0 +1,200 @@
+//! Parameter values as they can be typed in: with a unit, like `1600dpi` or `5in/s`,
+//! or as a simple expression, like `1/30`.
+//!
+//! Input speeds are converted to the counts/ms the curves take, those of a 1000 DPI mouse.
+//! That's the same as in/s whatever the mouse's DPI: a 1600 DPI mouse moving at 1 in/s
+//! gives 1.6 counts/ms, which the driver scales by 1000/1600 in `f_accelerate`.
+
+use crate::{Error, Param};
+
+/// The units values can be typed in, the unit of the parameter they're for,
+/// and how many of the unit make one of the parameter's.
+const UNITS: &[(&str, &str, f64)] = &[
+    ("dpi", "dpi", 1.0),
+    ("Hz", "Hz", 1.0),
+    ("counts/ms", "counts/ms", 1.0),
+    ("counts/s", "counts/ms", 1000.0),
+    ("in/s", "counts/ms", 1.0),
+    ("cm/s", "counts/ms", 2.54),
+    ("mm/s", "counts/ms", 25.4),
+    ("m/s", "counts/ms", 0.0254),
+];
+
+/// Parse a value for `param`, which can end with a unit, if it's one for the parameter.
+pub fn parse_param_value(param: Param, input: &str) -> crate::Result<f64> {
+    let input = input.trim();
+    let invalid_number = || Error::InvalidNumber(input.to_string());
+
+    let (expression, per_unit) = match split_unit(input) {
+        Some((expression, unit, param_unit, per_unit)) => {
+            match param.info().unit {
+                Some(expected) if expected == param_unit => {}
+                Some(expected) => {
+                    return Err(Error::invalid_value(
+                        param,
+                        format!("{} is in {expected}, not {unit}", param.display_name()),
+                    ));
+                }
+                None => {
+                    return Err(Error::invalid_value(
+                        param,
+                        format!("{} has no unit", param.display_name()),
+                    ));
+                }
+            }
+            (expression, per_unit)
+        }
+        None => (input, 1.0),
+    };
+
+    let mut parser = Parser {
+        input: expression.as_bytes(),
+        position: 0,
+    };
+    let value = parser.expression().ok_or_else(invalid_number)?;
+    parser.skip_whitespace();
+    if parser.position != parser.input.len() || !value.is_finite() {
+        return Err(invalid_number());
+    }
+
+    Ok(value / per_unit)
+}
+
+/// The expression, and the unit it ends with, if it's one we know.
+fn split_unit(input: &str) -> Option<(&str, &'static str, &'static str, f64)> {
+    UNITS.iter().find_map(|&(unit, param_unit, per_unit)| {
+        let expression = input.strip_suffix(unit)?;
+        // So that `m/s` isn't found at the end of `cm/s`.
+        if expression.ends_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        Some((expression.trim_end(), unit, param_unit, per_unit))
+    })
+}
+
+/// Sums and products of numbers, with parentheses.
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            match self.peek() {
+                Some(b'+') => {
+                    self.position += 1;
+                    value += self.term()?;
+                }
+                Some(b'-') => {
+                    self.position += 1;
+                    value -= self.term()?;
+                }
+                _ => return Some(value),
+            }
+        }
+    }
+
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        loop {
+            match self.peek() {
+                Some(b'*') => {
+                    self.position += 1;
+                    value *= self.factor()?;
+                }
+                Some(b'/') => {
+                    self.position += 1;
+                    value /= self.factor()?;
+                }
+                _ => return Some(value),
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            b'-' => {
+                self.position += 1;
+                Some(-self.factor()?)
+            }
+            b'(' => {
+                self.position += 1;
+                let value = self.expression()?;
+                if self.peek()? != b')' {
+                    return None;
+                }
+                self.position += 1;
+                Some(value)
+            }
+            _ => {
+                let start = self.position;
+                while let Some(&c) = self.input.get(self.position) {
+                    let in_exponent = self.position > start
+                        && matches!(self.input[self.position - 1], b'e' | b'E');
+                    if !(c.is_ascii_digit()
+                        || matches!(c, b'.' | b'e' | b'E')
+                        || in_exponent && matches!(c, b'+' | b'-'))
+                    {
+                        break;
+                    }
+                    self.position += 1;
+                }
+                std::str::from_utf8(&self.input[start..self.position])
+                    .ok()?
+                    .parse()
+                    .ok()
+            }
+        }
+    }
+
+    /// The next character that's not a space.
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .input
+            .get(self.position)
+            .is_some_and(|c| c.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn parses_values_with_units_and_expressions() {
+    let parse = |param, input| parse_param_value(param, input).ok();
+
+    assert_eq!(parse(Param::AccelLinear, "0.3"), Some(0.3));
+    assert_eq!(parse(Param::AccelLinear, " 1/30 "), Some(1.0 / 30.0));
+    assert_eq!(parse(Param::AccelLinear, "-(1 + 2) * 2 / 4"), Some(-1.5));
+    assert_eq!(parse(Param::AccelLinear, "2e-2"), Some(0.02));
+    assert_eq!(parse(Param::InputDpi, "1600dpi"), Some(1600.0));
+    assert_eq!(parse(Param::InputDpi, "800 * 2 dpi"), Some(1600.0));
+    assert_eq!(parse(Param::OffsetLinear, "5in/s"), Some(5.0));
+    assert_eq!(parse(Param::OffsetLinear, "2.54 cm/s"), Some(1.0));
+    assert_eq!(parse(Param::SyncSpeed, "2000 counts/s"), Some(2.0));
+    assert_eq!(parse(Param::RefreshRateWindows, "144Hz"), Some(144.0));
+
+    assert_eq!(parse(Param::AccelLinear, "1/"), None);
+    assert_eq!(parse(Param::AccelLinear, "(1"), None);
+    assert_eq!(parse(Param::AccelLinear, "1/0"), None);
+    assert_eq!(parse(Param::AccelLinear, "two"), None);
+    assert!(matches!(
+        parse_param_value(Param::Limit, "5in/s"),
+        Err(Error::InvalidValue {
+            param: Param::Limit,
+            ..
+        })
+    ));
+    assert!(matches!(
+        parse_param_value(Param::InputDpi, "5in/s"),
+        Err(Error::InvalidValue { .. })
+    ));
+}
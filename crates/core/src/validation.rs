@@ -0,0 +1,259 @@
+//! Checks on combinations of parameters that each make sense alone,
+//! but together make a broken, or likely unintended, curve.
+//!
+//! Each value on its own is already checked when it's set,
+//! see `validate_param_value`.
+
+use std::{f64::consts::LN_2, fmt::Display};
+
+use crate::{
+    AccelMode, AllParamArgs, Error, Param,
+    fixedptc::{FIXEDPT_BITS, FIXEDPT_WBITS},
+};
+
+/// The range of input speeds, in counts/ms normalized to 1000 DPI,
+/// we expect a curve to have to handle.
+const INPUT_SPEED_RANGE: (f64, f64) = (0.01, 500.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Likely a mistake, but it's a working curve.
+    Warning,
+    /// The driver would compute a broken curve.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    /// The parameters that together cause the issue.
+    pub params: &'static [Param],
+    pub message: String,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.params.iter().map(|p| p.name()).collect();
+        write!(f, "{} ({})", self.message, names.join(", "))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Validation {
+    pub issues: Vec<Issue>,
+}
+
+impl Validation {
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// The issues that `param` is involved in.
+    pub fn for_param(&self, param: Param) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(move |i| i.params.contains(&param))
+    }
+
+    /// Fail with every error, if there's any, otherwise give back the warnings.
+    pub fn into_result(self) -> crate::Result<Self> {
+        if !self.has_errors() {
+            return Ok(self);
+        }
+
+        Err(Error::InvalidCurve(self.errors().cloned().collect()))
+    }
+
+    fn error(&mut self, params: &'static [Param], message: impl Into<String>) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            params,
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, params: &'static [Param], message: impl Into<String>) {
+        self.issues.push(Issue {
+            severity: Severity::Warning,
+            params,
+            message: message.into(),
+        });
+    }
+}
+
+/// Check the common parameters and those of `mode`'s curve, together.
+pub fn validate_params(mode: AccelMode, args: &AllParamArgs) -> Validation {
+    let mut validation = Validation::default();
+    let value = |param: Param| f64::from(args.get(param));
+
+    if value(Param::SensMult) <= 0.0 {
+        validation.warning(
+            &[Param::SensMult],
+            "a sens multiplier that's not positive stops or reverses the cursor",
+        );
+    }
+
+    match mode {
+        AccelMode::Linear => {
+            check_output_cap(
+                &mut validation,
+                value(Param::OutputCapLinear),
+                &[Param::OutputCapLinear],
+            );
+        }
+        AccelMode::Classic => {
+            let power = value(Param::PowerClassic);
+            if power < 1.0 && value(Param::OffsetClassic) > 0.0 {
+                validation.error(
+                    &[Param::PowerClassic, Param::OffsetClassic],
+                    "a power below 1 with an offset makes the sensitivity jump right past the offset",
+                );
+            }
+
+            check_output_cap(
+                &mut validation,
+                value(Param::OutputCapClassic),
+                &[Param::OutputCapClassic],
+            );
+        }
+        AccelMode::Natural => {}
+        AccelMode::Synchronous => check_synchronous(&mut validation, args),
+        AccelMode::Jump | AccelMode::Power | AccelMode::Windows | AccelMode::Macos => {}
+        // The points were checked when the table was made.
+        AccelMode::LookupTable => {}
+    }
+
+    validation
+}
+
+/// A cap between 0 (no cap) and 1 turns the acceleration into deceleration.
+fn check_output_cap(validation: &mut Validation, cap: f64, params: &'static [Param]) {
+    if cap != 0.0 && cap < 1.0 {
+        validation.warning(
+            params,
+            "an output cap below 1 turns the acceleration into deceleration, use 0 for no cap",
+        );
+    }
+}
+
+/// The curve raises `gamma / ln(motivity) * |ln(speed) - ln(sync_speed)|` to the power of
+/// `0.5 / smooth`, with `fpt_exp`, which overflows past `ln` of the largest fixed-point number.
+/// Its `fpt_tanh` is fine with any number, it saturates to 1 before `e^(2x)` would overflow.
+fn check_synchronous(validation: &mut Validation, args: &AllParamArgs) {
+    let gamma = f64::from(args.gamma);
+    let smooth = f64::from(args.smooth);
+    let motivity = f64::from(args.motivity);
+    let sync_speed = f64::from(args.sync_speed);
+
+    let sharpness = if smooth == 0.0 { 16.0 } else { 0.5 / smooth };
+    if sharpness >= 16.0 || motivity <= 1.0 || sync_speed <= 0.0 {
+        // It's a linear clamp then, or values already rejected on their own.
+        return;
+    }
+
+    let max_exponent = (FIXEDPT_WBITS - 1) as f64 * LN_2;
+    let (slowest, fastest) = INPUT_SPEED_RANGE;
+
+    let overflows = [slowest, fastest].into_iter().any(|speed| {
+        let log_space = gamma / motivity.ln() * (speed.ln() - sync_speed.ln()).abs();
+        log_space > 0.0 && sharpness * log_space.ln() >= max_exponent
+    });
+
+    if overflows {
+        validation.error(
+            &[Param::SyncSpeed, Param::Gamma, Param::Smooth, Param::Motivity],
+            format!(
+                "the sync speed is too far from input speeds between {slowest} and {fastest} for this gamma and smooth, \
+                 the curve would overflow {FIXEDPT_BITS}-bit fixed-point numbers"
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn validate_params_finds_broken_combinations() {
+    use crate::fixedptc::Fpt;
+
+    let args = |overrides: &[(Param, f64)]| {
+        AllParamArgs::try_from_fn(|p| {
+            let value = overrides
+                .iter()
+                .find(|(o, _)| *o == p)
+                .map_or(1.0, |&(_, v)| v);
+            Ok(Fpt::from(value))
+        })
+        .unwrap()
+    };
+
+    let ok = args(&[
+        (Param::OutputCapLinear, 0.0),
+        (Param::Smooth, 0.5),
+        (Param::Motivity, 1.5),
+        (Param::SyncSpeed, 5.0),
+    ]);
+    assert_eq!(
+        validate_params(AccelMode::Linear, &ok),
+        Validation::default()
+    );
+    assert_eq!(
+        validate_params(AccelMode::Synchronous, &ok),
+        Validation::default()
+    );
+
+    let classic = args(&[(Param::PowerClassic, 0.5), (Param::OffsetClassic, 2.0)]);
+    let validation = validate_params(AccelMode::Classic, &classic);
+    assert!(validation.has_errors());
+    assert_eq!(validation.for_param(Param::PowerClassic).count(), 1);
+    assert!(
+        validate_params(AccelMode::Linear, &classic)
+            .issues
+            .is_empty()
+    );
+
+    let capped = args(&[(Param::OutputCapLinear, 0.5)]);
+    let validation = validate_params(AccelMode::Linear, &capped);
+    assert!(!validation.has_errors());
+    assert_eq!(validation.warnings().count(), 1);
+    assert!(validation.into_result().is_ok());
+
+    let sync = args(&[
+        (Param::Gamma, 10.0),
+        (Param::Smooth, 0.05),
+        (Param::Motivity, 1.5),
+        (Param::SyncSpeed, 5.0),
+    ]);
+    assert!(validate_params(AccelMode::Synchronous, &sync).has_errors());
+
+    // Far from the sync speed, `fpt_tanh` saturates rather than overflowing,
+    // so the curves we let through are right across the whole range.
+    for (gamma, sync_speed) in [(1.0, 5.0), (0.8, 32.0)] {
+        let sync = args(&[
+            (Param::Gamma, gamma),
+            (Param::Smooth, 0.5),
+            (Param::Motivity, 1.5),
+            (Param::SyncSpeed, sync_speed),
+        ]);
+        assert!(!validate_params(AccelMode::Synchronous, &sync).has_errors());
+
+        let (slowest, fastest) = INPUT_SPEED_RANGE;
+        for speed in [slowest, 0.05, 0.125, fastest] {
+            let (sens, _) = crate::sensitivity(speed, AccelMode::Synchronous, &sync);
+            let (expected, _) = crate::reference_sensitivity(speed, AccelMode::Synchronous, &sync);
+            assert!(
+                (sens - expected).abs() < 1e-2,
+                "{sens} != {expected} at {speed}"
+            );
+        }
+    }
+}
@@ -3,9 +3,14 @@
 
 #include "accel/linear.h"
 #include "accel/classic.h"
+#include "accel/jump.h"
+#include "accel/lut.h"
+#include "accel/macos.h"
 #include "accel/mode.h"
 #include "accel/natural.h"
+#include "accel/power.h"
 #include "accel/synchronous.h"
+#include "accel/windows.h"
 #include "dbg.h"
 #include "fixedptc.h"
 #include "math.h"
@@ -16,6 +21,12 @@ union __accel_args {
   struct linear_curve_args linear;
   struct classic_curve_args classic;
   struct synchronous_curve_args synchronous;
+  struct jump_curve_args jump;
+  struct power_curve_args power;
+  struct windows_curve_args windows;
+  struct macos_curve_args macos;
+  // Much bigger than the others' arguments, so it's shared rather than copied.
+  const struct lut_curve_args *lookup_table;
 };
 
 struct accel_args {
@@ -35,33 +46,53 @@ const fpt NORMALIZED_DPI = fpt_fromint(1000);
  *
  */
 static inline struct vector sensitivity(fpt input_speed,
-                                        struct accel_args args) {
+                                        const struct accel_args *args) {
   fpt sens;
 
-  switch (args.tag) {
+  switch (args->tag) {
+  case lookup_table:
+    dbg("accel mode %d: lookup_table", args->tag);
+    sens = __lut_sens_fun(input_speed, args->args.lookup_table);
+    break;
+  case macos:
+    dbg("accel mode %d: macos", args->tag);
+    sens = __macos_sens_fun(input_speed, args->args.macos);
+    break;
+  case windows:
+    dbg("accel mode %d: windows", args->tag);
+    sens = __windows_sens_fun(input_speed, args->input_dpi, args->args.windows);
+    break;
+  case power:
+    dbg("accel mode %d: power", args->tag);
+    sens = __power_sens_fun(input_speed, args->args.power);
+    break;
+  case jump:
+    dbg("accel mode %d: jump", args->tag);
+    sens = __jump_sens_fun(input_speed, args->args.jump);
+    break;
   case synchronous:
-    dbg("accel mode %d: synchronous", args.tag);
-    sens = __synchronous_sens_fun(input_speed, args.args.synchronous);
+    dbg("accel mode %d: synchronous", args->tag);
+    sens = __synchronous_sens_fun(input_speed, args->args.synchronous);
     break;
   case natural:
-    dbg("accel mode %d: natural", args.tag);
-    sens = __natural_sens_fun(input_speed, args.args.natural);
+    dbg("accel mode %d: natural", args->tag);
+    sens = __natural_sens_fun(input_speed, args->args.natural);
     break;
   case classic:
-    dbg("accel mode %d: classic", args.tag);
-    sens = __classic_sens_fun(input_speed, args.args.classic);
+    dbg("accel mode %d: classic", args->tag);
+    sens = __classic_sens_fun(input_speed, args->args.classic);
     break;
   case linear:
   default:
-    dbg("accel mode %d: linear", args.tag);
-    sens = __linear_sens_fun(input_speed, args.args.linear);
+    dbg("accel mode %d: linear", args->tag);
+    sens = __linear_sens_fun(input_speed, args->args.linear);
   }
-  sens = fpt_mul(sens, args.sens_mult);
-  return (struct vector){sens, fpt_mul(sens, args.yx_ratio)};
+  sens = fpt_mul(sens, args->sens_mult);
+  return (struct vector){sens, fpt_mul(sens, args->yx_ratio)};
 }
 
 static inline void f_accelerate(int *x, int *y, fpt time_interval_ms,
-                                struct accel_args args) {
+                                const struct accel_args *args) {
   /* AccelResult result = {.x = 0, .y = 0}; */
 
   static fpt carry_x = 0;
@@ -74,7 +105,7 @@ static inline void f_accelerate(int *x, int *y, fpt time_interval_ms,
   dbg("in: x (fpt conversion) %s", fptoa(dx));
   dbg("in: y (fpt conversion) %s", fptoa(dy));
 
-  fpt dpi_factor = fpt_div(NORMALIZED_DPI, args.input_dpi);
+  fpt dpi_factor = fpt_div(NORMALIZED_DPI, args->input_dpi);
   dbg("dpi adjustment factor:     %s", fptoa(dpi_factor));
   dx = fpt_mul(dx, dpi_factor);
   dy = fpt_mul(dy, dpi_factor);
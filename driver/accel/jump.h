@@ -0,0 +1,61 @@
+#ifndef __ACCEL_JUMP_H_
+#define __ACCEL_JUMP_H_
+
+#include "../dbg.h"
+#include "../fixedptc.h"
+
+struct jump_curve_args {
+  fpt speed;
+  fpt sens;
+  fpt smooth;
+};
+
+/**
+ * How far from the jump speed the transition is over, in widths of the
+ * transition: past it, the sensitivity is less than the smallest fixed-point
+ * number away from 1 or the jump's sensitivity, since e^-(2pi * this) is.
+ */
+#define JUMP_TRANSITION_END                                                    \
+  fpt_rconst(FIXEDPT_FBITS * 0.69314718055994530942 /                          \
+             (2 * 3.14159265358979323846))
+
+/**
+ * Sensitivity Function for the `Jump` curve, as in rawaccel: 1 below the jump
+ * speed, and the jump's sensitivity above it.
+ *
+ * With some smoothing, the sensitivity goes from one to the other along a
+ * logistic function, over a width of `smooth` times the jump speed.
+ * It's a sharp jump when that's narrower than 1 count/ms.
+ */
+static inline fpt __jump_sens_fun(fpt input_speed,
+                                  struct jump_curve_args args) {
+  dbg("jump: speed               %s", fptoa(args.speed));
+  dbg("jump: sens                %s", fptoa(args.sens));
+  dbg("jump: smooth              %s", fptoa(args.smooth));
+
+  fpt width = fpt_mul(args.smooth, args.speed);
+  if (width < FIXEDPT_ONE) {
+    return input_speed < args.speed ? FIXEDPT_ONE : args.sens;
+  }
+
+  fpt distance = input_speed - args.speed;
+  fpt transition_end = fpt_mul(JUMP_TRANSITION_END, width);
+  if (distance >= transition_end) {
+    return args.sens;
+  }
+  if (distance <= -transition_end) {
+    return FIXEDPT_ONE;
+  }
+
+  // The logistic function 1 / (1 + e^-x), with e only ever raised to a
+  // negative power, so that it doesn't overflow.
+  fpt rate = fpt_div(FIXEDPT_TWO_PI, width);
+  fpt decay = fpt_exp(-fpt_mul(rate, fpt_abs(distance)));
+  fpt logistic = distance >= 0 ? fpt_div(FIXEDPT_ONE, FIXEDPT_ONE + decay)
+                               : fpt_div(decay, FIXEDPT_ONE + decay);
+  dbg("jump: logistic            %s", fptoa(logistic));
+
+  return FIXEDPT_ONE + fpt_mul(args.sens - FIXEDPT_ONE, logistic);
+}
+
+#endif // !__ACCEL_JUMP_H_
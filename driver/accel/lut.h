@@ -0,0 +1,140 @@
+#ifndef __ACCEL_LUT_H_
+#define __ACCEL_LUT_H_
+
+#include "../dbg.h"
+#include "../fixedptc.h"
+#include "../utils.h"
+
+/**
+ * How many points the look-up table takes, which can be changed when building
+ * the module, e.g. with `EXTRA_CFLAGS="-DLUT_MAX_POINTS=128"`, and the CLI and
+ * TUI with `LUT_MAX_POINTS=128` so that they check tables against the same
+ * number of points.
+ */
+#ifndef LUT_MAX_POINTS
+#define LUT_MAX_POINTS 64
+#endif
+
+/**
+ * The points of the curve, by increasing input speed.
+ */
+struct lut_curve_args {
+  unsigned long len;
+  fpt speeds[LUT_MAX_POINTS];
+  fpt sens[LUT_MAX_POINTS];
+};
+
+/**
+ * Parse the points from a string of raw fixed-point numbers, separated by
+ * commas or spaces, each point's input speed followed by its sensitivity,
+ * e.g. "0,65536,524288,98304" for (0, 1) and (8, 1.5) with 32-bit numbers.
+ *
+ * Returns 0, or -1 if the string isn't made of whole points, there are more
+ * than LUT_MAX_POINTS, a number doesn't fit in an fpt, or the speeds don't
+ * increase from one point to the next, in which case `args` is left as it was.
+ */
+static inline int lut_parse(const char *data, struct lut_curve_args *args) {
+  const fptu largest = ((fptu)-1) >> 1;
+  struct lut_curve_args parsed = {0};
+  unsigned long count = 0;
+  fptu n = 0;
+  int sign = 0;
+  int has_digits = 0;
+
+  for (int idx = 0;; idx++) {
+    char c = data[idx];
+
+    if (is_digit(c)) {
+      // Past the largest fpt, the number would wrap around into another one.
+      fptu digit = c - '0';
+      if (n > (largest - digit) / 10) {
+        return -1;
+      }
+      n = n * 10 + digit;
+      has_digits = 1;
+      continue;
+    }
+
+    if (c == '-' && !sign && !has_digits) {
+      sign = 1;
+      continue;
+    }
+
+    if (sign && !has_digits) {
+      return -1;
+    }
+
+    if (has_digits) {
+      if (count == 2 * LUT_MAX_POINTS) {
+        return -1;
+      }
+
+      fpt value = sign ? -n : n;
+      if (count % 2 == 0) {
+        if (count > 0 && value <= parsed.speeds[count / 2 - 1]) {
+          return -1;
+        }
+        parsed.speeds[count / 2] = value;
+      } else {
+        parsed.sens[count / 2] = value;
+      }
+
+      count++;
+      n = 0;
+      sign = 0;
+      has_digits = 0;
+    }
+
+    if (c == '\0') {
+      break;
+    }
+
+    if (c != ',' && c != ' ' && c != '\n' && c != '\t') {
+      return -1;
+    }
+  }
+
+  if (count % 2 != 0) {
+    return -1;
+  }
+
+  parsed.len = count / 2;
+  *args = parsed;
+  return 0;
+}
+
+/**
+ * Sensitivity Function for the Look-Up Table, interpolating linearly
+ * between the points, and constant before the first and after the last.
+ * Without points, the sensitivity is 1.
+ *
+ * The table is taken by reference, unlike the other curves' arguments,
+ * since it's much bigger.
+ */
+static inline fpt __lut_sens_fun(fpt input_speed,
+                                 const struct lut_curve_args *args) {
+  unsigned long len = args->len < LUT_MAX_POINTS ? args->len : LUT_MAX_POINTS;
+  dbg("lut: points               %lu", len);
+
+  if (len == 0) {
+    return FIXEDPT_ONE;
+  }
+
+  if (input_speed <= args->speeds[0]) {
+    return args->sens[0];
+  }
+
+  for (unsigned long i = 1; i < len; i++) {
+    // Past the previous point, so this one's speed is greater than that one's.
+    if (input_speed <= args->speeds[i]) {
+      fpt t = fpt_div(input_speed - args->speeds[i - 1],
+                      args->speeds[i] - args->speeds[i - 1]);
+      return args->sens[i - 1] +
+             fpt_mul(t, args->sens[i] - args->sens[i - 1]);
+    }
+  }
+
+  return args->sens[len - 1];
+}
+
+#endif // !__ACCEL_LUT_H_
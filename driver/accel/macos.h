@@ -0,0 +1,44 @@
+#ifndef __ACCEL_MACOS_H_
+#define __ACCEL_MACOS_H_
+
+#include "../dbg.h"
+#include "../fixedptc.h"
+
+struct macos_curve_args {
+  fpt tracking_speed;
+};
+
+/**
+ * The input speed, in counts/ms, at which the sensitivity is halfway up the
+ * ramp.
+ */
+#define MACOS_RAMP_SPEED fpt_rconst(8)
+
+/**
+ * Sensitivity Function for the `macOS` curve, an approximation of macOS's
+ * pointer acceleration: 1 at slow speeds, ramping up smoothly to
+ * 1 + tracking_speed at fast ones.
+ *
+ * The ramp is u^2 / (1 + u^2), with u the input speed over MACOS_RAMP_SPEED,
+ * so that it starts flat, and slow movements stay precise.
+ */
+static inline fpt __macos_sens_fun(fpt input_speed,
+                                   struct macos_curve_args args) {
+  dbg("macos: tracking_speed     %s", fptoa(args.tracking_speed));
+
+  fpt u = fpt_div(input_speed, MACOS_RAMP_SPEED);
+  fpt ramp;
+  if (u < FIXEDPT_ONE) {
+    fpt u_squared = fpt_mul(u, u);
+    ramp = fpt_div(u_squared, FIXEDPT_ONE + u_squared);
+  } else {
+    // The same, over u^2, so that it doesn't overflow at high speeds.
+    fpt inverse = fpt_div(FIXEDPT_ONE, u);
+    ramp = fpt_div(FIXEDPT_ONE, FIXEDPT_ONE + fpt_mul(inverse, inverse));
+  }
+  dbg("macos: ramp               %s", fptoa(ramp));
+
+  return FIXEDPT_ONE + fpt_mul(args.tracking_speed, ramp);
+}
+
+#endif // !__ACCEL_MACOS_H_
@@ -1,6 +1,16 @@
 #ifndef __ACCEL_MODE_H
 #define __ACCEL_MODE_H
 
-enum accel_mode : unsigned char { linear, classic, natural, synchronous };
+enum accel_mode : unsigned char {
+  linear,
+  classic,
+  natural,
+  synchronous,
+  jump,
+  power,
+  windows,
+  macos,
+  lookup_table
+};
 
 #endif // !__ACCEL_MODE_H
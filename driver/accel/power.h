@@ -0,0 +1,74 @@
+#ifndef __ACCEL_POWER_H_
+#define __ACCEL_POWER_H_
+
+#include "../dbg.h"
+#include "../fixedptc.h"
+#include "../math.h"
+
+struct power_curve_args {
+  fpt scale;
+  fpt exponent;
+  fpt output_offset;
+  fpt output_cap;
+};
+
+/**
+ * The natural logarithm of the largest power of 1 / base we take, and of the
+ * inverse of the smallest power of a base below 1.
+ */
+#define POWER_MAX_LOG fpt_rconst((FIXEDPT_WBITS - 2) * 0.69314718055994530942)
+
+/**
+ * `fpt_pow`, which is only accurate for a base of at least 1, like `fpt_ln`:
+ * below 1, it's 1 / (1 / base)^exponent.
+ */
+static inline fpt power_fn(fpt base, fpt exponent) {
+  if (base >= FIXEDPT_ONE) {
+    return fpt_pow(base, exponent);
+  }
+
+  // The inverse of the two smallest numbers doesn't fit.
+  if (base <= 2) {
+    return 0;
+  }
+
+  fpt log_inverse = fpt_mul(fpt_ln(fpt_div(FIXEDPT_ONE, base)), exponent);
+  if (log_inverse >= POWER_MAX_LOG) {
+    return 0;
+  }
+
+  return fpt_div(FIXEDPT_ONE, fpt_exp(log_inverse));
+}
+
+/**
+ * Sensitivity Function for the `Power` curve, as in rawaccel:
+ * (scale * x)^exponent, offset by `output_offset`, and no more than
+ * `output_cap`, unless it's 0.
+ *
+ * Unlike the classic curve's (a*x)^p / x, the sensitivity doesn't start at 1,
+ * but at the output offset.
+ */
+static inline fpt __power_sens_fun(fpt input_speed,
+                                   struct power_curve_args args) {
+  dbg("power: scale              %s", fptoa(args.scale));
+  dbg("power: exponent           %s", fptoa(args.exponent));
+  dbg("power: output_offset      %s", fptoa(args.output_offset));
+  dbg("power: output_cap         %s", fptoa(args.output_cap));
+
+  fpt sens = args.output_offset;
+
+  // `fpt_ln` isn't defined at 0, where the power is 0 anyway.
+  fpt base = fpt_mul(args.scale, input_speed);
+  if (base > 0) {
+    sens += power_fn(base, args.exponent);
+  }
+  dbg("power: sens               %s", fptoa(sens));
+
+  if (args.output_cap > 0) {
+    sens = minsd(sens, args.output_cap);
+  }
+
+  return sens;
+}
+
+#endif // !__ACCEL_POWER_H_
@@ -0,0 +1,112 @@
+#ifndef __ACCEL_WINDOWS_H_
+#define __ACCEL_WINDOWS_H_
+
+#include "../dbg.h"
+#include "../fixedptc.h"
+
+struct windows_curve_args {
+  fpt slider;
+  fpt refresh_rate;
+  fpt screen_dpi;
+};
+
+#define WINDOWS_CURVE_POINTS 5
+
+/**
+ * Windows' default SmoothMouseXCurve and SmoothMouseYCurve: the mouse's speed
+ * and the pointer's, both in in/s.
+ */
+static const fpt WINDOWS_CURVE_MOUSE_SPEEDS[WINDOWS_CURVE_POINTS] = {
+    0, fpt_rconst(0.43), fpt_rconst(1.25), fpt_rconst(3.86), fpt_rconst(40)};
+static const fpt WINDOWS_CURVE_POINTER_SPEEDS[WINDOWS_CURVE_POINTS] = {
+    0, fpt_rconst(1.37), fpt_rconst(5.30), fpt_rconst(24.30),
+    fpt_rconst(568)};
+
+/**
+ * The refresh rate Windows measures the speeds at, whatever the display's.
+ */
+#define WINDOWS_FRAME_RATE fpt_rconst(60)
+
+#define WINDOWS_SLIDER_NOTCHES 11
+
+/**
+ * The multipliers of the pointer speed slider's notches, from 1 to 11, with
+ * "Enhance pointer precision" on. The 6th, the default, is 1.
+ */
+static const fpt WINDOWS_SLIDER_MULTIPLIERS[WINDOWS_SLIDER_NOTCHES] = {
+    fpt_rconst(0.1), fpt_rconst(0.2), fpt_rconst(0.4), fpt_rconst(0.6),
+    fpt_rconst(0.8), fpt_rconst(1),   fpt_rconst(1.2), fpt_rconst(1.4),
+    fpt_rconst(1.6), fpt_rconst(1.8), fpt_rconst(2)};
+
+/**
+ * The DPI Windows takes any mouse to have.
+ */
+#define WINDOWS_MOUSE_DPI fpt_rconst(400)
+
+/**
+ * The DPI of the mouse the input speed is normalized to.
+ */
+#define WINDOWS_NORMALIZED_DPI fpt_rconst(1000)
+
+/**
+ * Sensitivity Function for the `Windows` curve: the pointer ballistics of
+ * "Enhance pointer precision", with Windows' default curve.
+ *
+ * Windows turns the mouse's speed into the pointer's along the piecewise
+ * linear curve through those points, extended past the last one. It doesn't
+ * know the mouse's DPI, so it takes its counts for those of a 400 DPI mouse,
+ * and the counts of a frame for those of 1/60 s: at a higher DPI, or a lower
+ * refresh rate, it takes the mouse to be faster than it is.
+ *
+ * The gain, the pointer's speed over the mouse's, is then scaled by the
+ * slider's notch, rounded to the nearest one, and turned into pixels per count
+ * with the screen's DPI. With the defaults, a slow 1000 DPI mouse moves the
+ * pointer 1.37 / 0.43 * 96 / 400 ~= 0.76 pixels per count, as on Windows.
+ */
+static inline fpt __windows_sens_fun(fpt input_speed, fpt input_dpi,
+                                     struct windows_curve_args args) {
+  dbg("windows: slider           %s", fptoa(args.slider));
+  dbg("windows: refresh_rate     %s", fptoa(args.refresh_rate));
+  dbg("windows: screen_dpi       %s", fptoa(args.screen_dpi));
+
+  // The mouse's counts per count of the input speed, as Windows takes them.
+  fpt counts = fpt_div(input_dpi, WINDOWS_MOUSE_DPI);
+  fpt mouse_speed =
+      fpt_mul(fpt_mul(input_speed, counts),
+              fpt_div(WINDOWS_FRAME_RATE, args.refresh_rate));
+  dbg("windows: mouse_speed      %s", fptoa(mouse_speed));
+
+  int notch = fpt_toint(args.slider + FIXEDPT_ONE_HALF);
+  if (notch < 1) {
+    notch = 1;
+  } else if (notch > WINDOWS_SLIDER_NOTCHES) {
+    notch = WINDOWS_SLIDER_NOTCHES;
+  }
+
+  int i = 1;
+  while (i < WINDOWS_CURVE_POINTS - 1 &&
+         mouse_speed > WINDOWS_CURVE_MOUSE_SPEEDS[i]) {
+    i++;
+  }
+
+  fpt slope = fpt_div(
+      WINDOWS_CURVE_POINTER_SPEEDS[i] - WINDOWS_CURVE_POINTER_SPEEDS[i - 1],
+      WINDOWS_CURVE_MOUSE_SPEEDS[i] - WINDOWS_CURVE_MOUSE_SPEEDS[i - 1]);
+
+  // The slope, plus the segment's intercept over the mouse's speed, rather
+  // than the pointer's speed over the mouse's, which can overflow past the
+  // last point. The first segment goes through the origin.
+  fpt gain = slope;
+  if (i > 1) {
+    fpt intercept = WINDOWS_CURVE_POINTER_SPEEDS[i - 1] -
+                    fpt_mul(slope, WINDOWS_CURVE_MOUSE_SPEEDS[i - 1]);
+    gain += fpt_div(intercept, mouse_speed);
+  }
+  dbg("windows: gain             %s", fptoa(gain));
+
+  return fpt_mul(
+      fpt_mul(gain, WINDOWS_SLIDER_MULTIPLIERS[notch - 1]),
+      fpt_mul(fpt_div(args.screen_dpi, WINDOWS_NORMALIZED_DPI), counts));
+}
+
+#endif // !__ACCEL_WINDOWS_H_
@@ -19,6 +19,35 @@ static struct accel_args collect_args(void) {
   accel.input_dpi = atofp(PARAM_INPUT_DPI);
 
   switch (mode) {
+  case lookup_table: {
+    // Only valid under the RCU read lock that accelerate() holds.
+    const struct lut_table *table = rcu_dereference(PARAM_LUT_DATA);
+    accel.args.lookup_table = table ? &table->args : &LUT_NO_POINTS;
+    break;
+  }
+  case macos: {
+    accel.args.macos.tracking_speed = atofp(PARAM_MACOS_TRACKING_SPEED);
+    break;
+  }
+  case windows: {
+    accel.args.windows.slider = atofp(PARAM_WINDOWS_SLIDER);
+    accel.args.windows.refresh_rate = atofp(PARAM_WINDOWS_REFRESH_RATE);
+    accel.args.windows.screen_dpi = atofp(PARAM_WINDOWS_SCREEN_DPI);
+    break;
+  }
+  case power: {
+    accel.args.power.scale = atofp(PARAM_POWER_SCALE);
+    accel.args.power.exponent = atofp(PARAM_POWER_EXPONENT);
+    accel.args.power.output_offset = atofp(PARAM_POWER_OUTPUT_OFFSET);
+    accel.args.power.output_cap = atofp(PARAM_POWER_OUTPUT_CAP);
+    break;
+  }
+  case jump: {
+    accel.args.jump.speed = atofp(PARAM_JUMP_SPEED);
+    accel.args.jump.sens = atofp(PARAM_JUMP_SENS);
+    accel.args.jump.smooth = atofp(PARAM_JUMP_SMOOTH);
+    break;
+  }
   case synchronous: {
     accel.args.synchronous.gamma = atofp(PARAM_GAMMA);
     accel.args.synchronous.smooth = atofp(PARAM_SMOOTH);
@@ -28,22 +57,22 @@ static struct accel_args collect_args(void) {
   }
   case natural: {
     accel.args.natural.decay_rate = atofp(PARAM_DECAY_RATE);
-    accel.args.natural.offset = atofp(PARAM_OFFSET);
+    accel.args.natural.offset = atofp(PARAM_NATURAL_OFFSET);
     accel.args.natural.limit = atofp(PARAM_LIMIT);
     break;
   }
     case classic: {
-    accel.args.classic.accel = atofp(PARAM_ACCEL);
+    accel.args.classic.accel = atofp(PARAM_CLASSIC_ACCEL);
     accel.args.classic.power = atofp(PARAM_POWER);
-    accel.args.classic.offset = atofp(PARAM_OFFSET);
-    accel.args.classic.output_cap = atofp(PARAM_OUTPUT_CAP);
+    accel.args.classic.offset = atofp(PARAM_CLASSIC_OFFSET);
+    accel.args.classic.output_cap = atofp(PARAM_CLASSIC_OUTPUT_CAP);
     break;
   }
   case linear:
   default: {
-    accel.args.linear.accel = atofp(PARAM_ACCEL);
-    accel.args.linear.offset = atofp(PARAM_OFFSET);
-    accel.args.linear.output_cap = atofp(PARAM_OUTPUT_CAP);
+    accel.args.linear.accel = atofp(PARAM_LINEAR_ACCEL);
+    accel.args.linear.offset = atofp(PARAM_LINEAR_OFFSET);
+    accel.args.linear.output_cap = atofp(PARAM_LINEAR_OUTPUT_CAP);
   }
   };
   return accel;
@@ -60,6 +89,7 @@ static inline void accelerate(int *x, int *y) {
 
   static ktime_t last_time;
   ktime_t now = ktime_get();
+  struct accel_args args;
 
 #if FIXEDPT_BITS == 64
   s64 unit_time = ktime_to_ns(now - last_time);
@@ -83,7 +113,12 @@ static inline void accelerate(int *x, int *y) {
       fptoa(millisecond));
 #endif
 
-  return f_accelerate(x, y, millisecond, collect_args());
+  // The look-up table stays valid until the movement's done, even if LUT_DATA
+  // is written meanwhile.
+  rcu_read_lock();
+  args = collect_args();
+  f_accelerate(x, y, millisecond, &args);
+  rcu_read_unlock();
 }
 
 #endif // !_ACCELK_H_
@@ -70,9 +70,17 @@
 
 #include "utils.h"
 
+/*
+ * Unless it's set for the build, like the Makefile does, it's the width of a
+ * `long` for the target, so that DKMS or a cross-compile gets it right.
+ */
 #ifndef FIXEDPT_BITS
+#if __SIZEOF_LONG__ == 4
+#define FIXEDPT_BITS 32
+#else
 #define FIXEDPT_BITS 64
 #endif
+#endif
 
 #ifdef __KERNEL__
 #include <linux/math64.h>
@@ -160,8 +168,9 @@ static inline fpt div128_s64_s64(fpt dividend, fpt divisor) {
 static inline fpt fpt_div(fpt A, fpt B) {
 #if FIXEDPT_BITS == 64
   return div128_s64_s64(A, B);
-#endif
+#else
   return (((fptd)A << FIXEDPT_FBITS) / (fptd)B);
+#endif
 }
 
 /*
@@ -380,6 +389,18 @@ static inline fpt fpt_exp(fpt fp) {
 
 /* Returns the hyperbolic tangent of the given fpt number */
 static inline fpt fpt_tanh(fpt X) {
+  /*
+   * Past this, e^(2X) + 1 would overflow, while tanh(X) is already 1 to within
+   * a few units of the last place.
+   */
+  const fpt SATURATION =
+      fpt_rconst(((FIXEDPT_WBITS - 1) * 0.69314718055994530942 - 1) / 2);
+
+  if (X > SATURATION)
+    return FIXEDPT_ONE;
+  if (X < -SATURATION)
+    return -FIXEDPT_ONE;
+
   fpt e_to_the_2_x = fpt_exp(fpt_mul(FIXEDPT_TWO, X));
   fpt sinh = e_to_the_2_x - FIXEDPT_ONE;
   fpt cosh = e_to_the_2_x + FIXEDPT_ONE;
@@ -410,6 +431,11 @@ static inline fpt fpt_ln(fpt x) {
     xi >>= 1;
     log2++;
   }
+  // The series is only accurate from 1 to 2, so small numbers are scaled up.
+  while (xi < FIXEDPT_ONE) {
+    xi <<= 1;
+    log2--;
+  }
   f = xi - FIXEDPT_ONE;
   s = fpt_div(f, FIXEDPT_TWO + f);
   z = fpt_mul(s, s);
@@ -417,7 +443,7 @@ static inline fpt fpt_ln(fpt x) {
   R = fpt_mul(w, LG[1] + fpt_mul(w, LG[3] + fpt_mul(w, LG[5]))) +
       fpt_mul(z, LG[0] +
                      fpt_mul(w, LG[2] + fpt_mul(w, LG[4] + fpt_mul(w, LG[6]))));
-  return (fpt_mul(LN2, (log2 << FIXEDPT_FBITS)) + f - fpt_mul(s, f - R));
+  return (fpt_mul(LN2, log2 * FIXEDPT_ONE) + f - fpt_mul(s, f - R));
 }
 
 /* Returns the logarithm of the given base of the given fpt number */
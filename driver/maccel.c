@@ -25,6 +25,7 @@ err_free_chrdev:
 static void __exit driver_exit(void) {
   input_unregister_handler(&maccel_handler);
   destroy_char_device();
+  lut_data_free();
 }
 
 MODULE_LICENSE("GPL");
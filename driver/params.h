@@ -1,9 +1,14 @@
 #ifndef _PARAM_H_
 #define _PARAM_H_
 
+#include "accel/lut.h"
 #include "accel/mode.h"
 #include "fixedptc.h"
+#include "linux/errno.h"
 #include "linux/moduleparam.h"
+#include "linux/rcupdate.h"
+#include "linux/slab.h"
+#include "linux/sysfs.h"
 
 #define RW_USER_GROUP 0664
 
@@ -33,9 +38,27 @@ PARAM(INPUT_DPI, 65536000, // 1000 << 16
       "in/sec");
 #endif
 
-PARAM(ACCEL, 0, "Control the sensitivity calculation.");
-PARAM(OFFSET, 0, "Control the input speed past which to allow acceleration.");
-PARAM(OUTPUT_CAP, 0, "Control the maximum sensitivity.");
+// Every curve keeps its own values, so that tuning one doesn't clobber another.
+
+// For Linear Mode
+
+PARAM(LINEAR_ACCEL, 0,
+      "Control the sensitivity calculation of the 'linear' curve.");
+PARAM(LINEAR_OFFSET, 0,
+      "Control the input speed past which to allow acceleration, for the "
+      "'linear' curve.");
+PARAM(LINEAR_OUTPUT_CAP, 0,
+      "Control the maximum sensitivity of the 'linear' curve.");
+
+// For Classic Mode
+
+PARAM(CLASSIC_ACCEL, 0,
+      "Control the sensitivity calculation of the 'classic' curve.");
+PARAM(CLASSIC_OFFSET, 0,
+      "Control the input speed past which to allow acceleration, for the "
+      "'classic' curve.");
+PARAM(CLASSIC_OUTPUT_CAP, 0,
+      "Control the maximum sensitivity of the 'classic' curve.");
 
 #if FIXEDPT_BITS == 64
 PARAM(POWER, 8589934592, // 2 << 32
@@ -46,6 +69,10 @@ PARAM(POWER, 131072, // 2 << 16
 #endif
 // For Natural Mode
 
+PARAM(NATURAL_OFFSET, 0,
+      "Control the input speed past which to allow acceleration, for the "
+      "'natural' curve.");
+
 #if FIXEDPT_BITS == 64
 PARAM(DECAY_RATE, 429496730, // 0.1 << 32
       "Decay rate of the Natural curve.");
@@ -82,6 +109,147 @@ PARAM(SYNC_SPEED, 327680, // 5 << 16
       "Set The middle sensitivity between you min and max sensitivity");
 #endif
 
+// For Jump Mode
+
+#if FIXEDPT_BITS == 64
+PARAM(JUMP_SPEED, 64424509440, // 15 << 32
+      "Control the input speed at which the 'jump' curve jumps.");
+PARAM(JUMP_SENS, 6442450944, // 1.5 << 32
+      "Control the sensitivity the 'jump' curve jumps to.");
+PARAM(JUMP_SMOOTH, 2147483648, // 0.5 << 32
+      "Control how gradual the jump is, as a fraction of JUMP_SPEED.");
+#else
+PARAM(JUMP_SPEED, 983040, // 15 << 16
+      "Control the input speed at which the 'jump' curve jumps.");
+PARAM(JUMP_SENS, 98304, // 1.5 << 16
+      "Control the sensitivity the 'jump' curve jumps to.");
+PARAM(JUMP_SMOOTH, 32768, // 0.5 << 16
+      "Control how gradual the jump is, as a fraction of JUMP_SPEED.");
+#endif
+
+// For Power Mode
+
+PARAM(POWER_OUTPUT_OFFSET, 0,
+      "Control the sensitivity the 'power' curve starts at.");
+PARAM(POWER_OUTPUT_CAP, 0,
+      "Control the maximum sensitivity of the 'power' curve.");
+
+#if FIXEDPT_BITS == 64
+PARAM(POWER_SCALE, 4294967296, // 1 << 32
+      "Control the factor the input speed is scaled by, for the 'power' "
+      "curve.");
+PARAM(POWER_EXPONENT, 214748365, // 0.05 << 32
+      "Control the exponent the scaled input speed is raised to, for the "
+      "'power' curve.");
+#else
+PARAM(POWER_SCALE, 65536, // 1 << 16
+      "Control the factor the input speed is scaled by, for the 'power' "
+      "curve.");
+PARAM(POWER_EXPONENT, 3277, // 0.05 << 16
+      "Control the exponent the scaled input speed is raised to, for the "
+      "'power' curve.");
+#endif
+
+// For Windows Mode
+
+#if FIXEDPT_BITS == 64
+PARAM(WINDOWS_SLIDER, 25769803776, // 6 << 32
+      "Control the notch of the Windows pointer speed slider, from 1 to 11.");
+PARAM(WINDOWS_REFRESH_RATE, 257698037760, // 60 << 32
+      "Control the refresh rate of the display Windows is emulated on.");
+PARAM(WINDOWS_SCREEN_DPI, 412316860416, // 96 << 32
+      "Control the DPI of the display Windows is emulated on.");
+#else
+PARAM(WINDOWS_SLIDER, 393216, // 6 << 16
+      "Control the notch of the Windows pointer speed slider, from 1 to 11.");
+PARAM(WINDOWS_REFRESH_RATE, 3932160, // 60 << 16
+      "Control the refresh rate of the display Windows is emulated on.");
+PARAM(WINDOWS_SCREEN_DPI, 6291456, // 96 << 16
+      "Control the DPI of the display Windows is emulated on.");
+#endif
+
+// For macOS Mode
+
+#if FIXEDPT_BITS == 64
+PARAM(MACOS_TRACKING_SPEED, 4294967296, // 1 << 32
+      "Control how much the 'macos' curve speeds up fast movements.");
+#else
+PARAM(MACOS_TRACKING_SPEED, 65536, // 1 << 16
+      "Control how much the 'macos' curve speeds up fast movements.");
+#endif
+
+// For Lookup Table Mode
+
+// The points are parsed once, when they're written, rather than on every
+// movement like the numbers above, into a new table that replaces the old one
+// under RCU: a movement reads either table whole, never one being written.
+struct lut_table {
+  struct rcu_head rcu;
+  struct lut_curve_args args;
+};
+
+static struct lut_table __rcu *PARAM_LUT_DATA;
+
+// The table until points are written.
+static const struct lut_curve_args LUT_NO_POINTS = {0};
+
+static int lut_data_set(const char *value, const struct kernel_param *kp) {
+  struct lut_table *old;
+  struct lut_table *table = kmalloc(sizeof(*table), GFP_KERNEL);
+  if (!table) {
+    return -ENOMEM;
+  }
+
+  if (lut_parse(value, &table->args)) {
+    kfree(table);
+    return -EINVAL;
+  }
+
+  // Writes to a module's parameters are serialized by the kernel's param lock.
+  old = rcu_replace_pointer(PARAM_LUT_DATA, table, true);
+  if (old) {
+    kfree_rcu(old, rcu);
+  }
+  return 0;
+}
+
+static int lut_data_get(char *buffer, const struct kernel_param *kp) {
+  const struct lut_table *table;
+  const struct lut_curve_args *args;
+  int len = 0;
+
+  rcu_read_lock();
+  table = rcu_dereference(PARAM_LUT_DATA);
+  args = table ? &table->args : &LUT_NO_POINTS;
+  for (unsigned long i = 0; i < args->len; i++) {
+    len += sysfs_emit_at(buffer, len, "%s%lld,%lld", i ? "," : "",
+                         (long long)args->speeds[i], (long long)args->sens[i]);
+  }
+  rcu_read_unlock();
+
+  return len + sysfs_emit_at(buffer, len, "\n");
+}
+
+/**
+ * Free the look-up table, once no movement can read it anymore.
+ */
+static void lut_data_free(void) {
+  synchronize_rcu();
+  kfree(rcu_dereference_protected(PARAM_LUT_DATA, true));
+  RCU_INIT_POINTER(PARAM_LUT_DATA, NULL);
+}
+
+static const struct kernel_param_ops lut_data_ops = {
+    .set = lut_data_set,
+    .get = lut_data_get,
+};
+
+module_param_cb(LUT_DATA, &lut_data_ops, NULL, RW_USER_GROUP);
+MODULE_PARM_DESC(LUT_DATA,
+                 "The points of the 'lookup_table' curve, as comma separated "
+                 "fixed-point numbers, each input speed followed by its "
+                 "sensitivity.");
+
 // Flags
 #define PARAM_FLAG(param, default_value, desc)                                 \
   unsigned char PARAM_##param = default_value;                                 \
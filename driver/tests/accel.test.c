@@ -16,7 +16,7 @@ static int test_acceleration(const char *filename, struct accel_args args) {
       int x_out = x;
       int y_out = y;
 
-      f_accelerate(&x_out, &y_out, FIXEDPT_ONE, args);
+      f_accelerate(&x_out, &y_out, FIXEDPT_ONE, &args);
 
       char curr_debug_print[LINE_LEN];
 
@@ -50,6 +50,27 @@ static int test_linear_acceleration(const char *filename, fpt param_sens_mult,
   return test_acceleration(filename, args);
 }
 
+static int test_classic_acceleration(const char *filename, fpt param_sens_mult,
+                                     fpt param_yx_ratio, fpt param_accel,
+                                     fpt param_power, fpt param_offset,
+                                     fpt param_output_cap) {
+  struct classic_curve_args _args =
+      (struct classic_curve_args){.accel = param_accel,
+                                  .power = param_power,
+                                  .offset = param_offset,
+                                  .output_cap = param_output_cap};
+
+  struct accel_args args = {
+      .sens_mult = param_sens_mult,
+      .yx_ratio = param_yx_ratio,
+      .input_dpi = fpt_fromint(1000),
+      .tag = classic,
+      .args = (union __accel_args){.classic = _args},
+  };
+
+  return test_acceleration(filename, args);
+}
+
 static int test_natural_acceleration(const char *filename, fpt param_sens_mult,
                                      fpt param_yx_ratio, fpt param_decay_rate,
                                      fpt param_offset, fpt param_limit) {
@@ -91,6 +112,99 @@ static int test_synchronous_acceleration(const char *filename,
   return test_acceleration(filename, args);
 }
 
+static int test_jump_acceleration(const char *filename, fpt param_sens_mult,
+                                  fpt param_yx_ratio, fpt param_speed,
+                                  fpt param_sens, fpt param_smooth) {
+  struct jump_curve_args _args = (struct jump_curve_args){
+      .speed = param_speed, .sens = param_sens, .smooth = param_smooth};
+
+  struct accel_args args = {
+      .sens_mult = param_sens_mult,
+      .yx_ratio = param_yx_ratio,
+      .input_dpi = fpt_fromint(1000),
+      .tag = jump,
+      .args = (union __accel_args){.jump = _args},
+  };
+
+  return test_acceleration(filename, args);
+}
+
+static int test_power_acceleration(const char *filename, fpt param_sens_mult,
+                                   fpt param_yx_ratio, fpt param_scale,
+                                   fpt param_exponent, fpt param_output_offset,
+                                   fpt param_output_cap) {
+  struct power_curve_args _args =
+      (struct power_curve_args){.scale = param_scale,
+                                .exponent = param_exponent,
+                                .output_offset = param_output_offset,
+                                .output_cap = param_output_cap};
+
+  struct accel_args args = {
+      .sens_mult = param_sens_mult,
+      .yx_ratio = param_yx_ratio,
+      .input_dpi = fpt_fromint(1000),
+      .tag = power,
+      .args = (union __accel_args){.power = _args},
+  };
+
+  return test_acceleration(filename, args);
+}
+
+static int test_windows_acceleration(const char *filename,
+                                     fpt param_sens_mult, fpt param_yx_ratio,
+                                     fpt param_slider, fpt param_refresh_rate,
+                                     fpt param_screen_dpi) {
+  struct windows_curve_args _args =
+      (struct windows_curve_args){.slider = param_slider,
+                                  .refresh_rate = param_refresh_rate,
+                                  .screen_dpi = param_screen_dpi};
+
+  struct accel_args args = {
+      .sens_mult = param_sens_mult,
+      .yx_ratio = param_yx_ratio,
+      .input_dpi = fpt_fromint(1000),
+      .tag = windows,
+      .args = (union __accel_args){.windows = _args},
+  };
+
+  return test_acceleration(filename, args);
+}
+
+static int test_macos_acceleration(const char *filename, fpt param_sens_mult,
+                                   fpt param_yx_ratio,
+                                   fpt param_tracking_speed) {
+  struct macos_curve_args _args =
+      (struct macos_curve_args){.tracking_speed = param_tracking_speed};
+
+  struct accel_args args = {
+      .sens_mult = param_sens_mult,
+      .yx_ratio = param_yx_ratio,
+      .input_dpi = fpt_fromint(1000),
+      .tag = macos,
+      .args = (union __accel_args){.macos = _args},
+  };
+
+  return test_acceleration(filename, args);
+}
+
+static int test_lookup_table_acceleration(const char *filename,
+                                          fpt param_sens_mult,
+                                          fpt param_yx_ratio,
+                                          const char *points) {
+  struct lut_curve_args _args = {0};
+  assert(lut_parse(points, &_args) == 0);
+
+  struct accel_args args = {
+      .sens_mult = param_sens_mult,
+      .yx_ratio = param_yx_ratio,
+      .input_dpi = fpt_fromint(1000),
+      .tag = lookup_table,
+      .args = (union __accel_args){.lookup_table = &_args},
+  };
+
+  return test_acceleration(filename, args);
+}
+
 #define test_linear(sens_mult, yx_ratio, accel, offset, cap)                   \
   assert(test_linear_acceleration(                                             \
              "SENS_MULT-" #sens_mult "-ACCEL-" #accel "-OFFSET" #offset        \
@@ -98,6 +212,14 @@ static int test_synchronous_acceleration(const char *filename,
              fpt_rconst(sens_mult), fpt_rconst(yx_ratio), fpt_rconst(accel),   \
              fpt_rconst(offset), fpt_rconst(cap)) == 0);
 
+#define test_classic(sens_mult, yx_ratio, accel, power, offset, cap)          \
+  assert(test_classic_acceleration(                                            \
+             "Classic__SENS_MULT-" #sens_mult "-ACCEL-" #accel                 \
+             "-POWER-" #power "-OFFSET" #offset "-OUTPUT_CAP-" #cap            \
+             ".snapshot",                                                      \
+             fpt_rconst(sens_mult), fpt_rconst(yx_ratio), fpt_rconst(accel),   \
+             fpt_rconst(power), fpt_rconst(offset), fpt_rconst(cap)) == 0);
+
 #define test_natural(sens_mult, yx_ratio, decay_rate, offset, limit)           \
   assert(test_natural_acceleration(                                            \
              "Natural__SENS_MULT-" #sens_mult "-DECAY_RATE-" #decay_rate       \
@@ -116,6 +238,54 @@ static int test_synchronous_acceleration(const char *filename,
              fpt_rconst(smooth), fpt_rconst(motivity),                         \
              fpt_rconst(sync_speed)) == 0);
 
+#define test_jump(sens_mult, yx_ratio, speed, sens, smooth)                    \
+  assert(test_jump_acceleration(                                               \
+             "Jump__SENS_MULT-" #sens_mult "-SPEED-" #speed "-SENS-" #sens     \
+             "-SMOOTH" #smooth ".snapshot",                                    \
+             fpt_rconst(sens_mult), fpt_rconst(yx_ratio), fpt_rconst(speed),   \
+             fpt_rconst(sens), fpt_rconst(smooth)) == 0);
+
+#define test_power(sens_mult, yx_ratio, scale, exponent, output_offset, cap)    \
+  assert(test_power_acceleration(                                              \
+             "Power__SENS_MULT-" #sens_mult "-SCALE-" #scale                   \
+             "-EXPONENT-" #exponent "-OUTPUT_OFFSET-" #output_offset           \
+             "-OUTPUT_CAP-" #cap ".snapshot",                                  \
+             fpt_rconst(sens_mult), fpt_rconst(yx_ratio), fpt_rconst(scale),   \
+             fpt_rconst(exponent), fpt_rconst(output_offset),                  \
+             fpt_rconst(cap)) == 0);
+
+#define test_windows(sens_mult, yx_ratio, slider, refresh_rate, screen_dpi)     \
+  assert(test_windows_acceleration(                                            \
+             "Windows__SENS_MULT-" #sens_mult "-SLIDER-" #slider               \
+             "-REFRESH_RATE-" #refresh_rate "-SCREEN_DPI-" #screen_dpi         \
+             ".snapshot",                                                      \
+             fpt_rconst(sens_mult), fpt_rconst(yx_ratio), fpt_rconst(slider),  \
+             fpt_rconst(refresh_rate), fpt_rconst(screen_dpi)) == 0);
+
+#define test_macos(sens_mult, yx_ratio, tracking_speed)                        \
+  assert(test_macos_acceleration("Macos__SENS_MULT-" #sens_mult                \
+                                 "-TRACKING_SPEED-" #tracking_speed            \
+                                 ".snapshot",                                  \
+                                 fpt_rconst(sens_mult), fpt_rconst(yx_ratio),  \
+                                 fpt_rconst(tracking_speed)) == 0);
+
+/**
+ * The points are given as numbers, then converted to the raw fixed-point
+ * numbers LUT_DATA takes.
+ */
+#define test_lookup_table(sens_mult, yx_ratio, s0, y0, s1, y1, s2, y2)         \
+  do {                                                                         \
+    char points[128];                                                          \
+    sprintf(points, "%lld,%lld,%lld,%lld,%lld,%lld",                           \
+            (long long)fpt_rconst(s0), (long long)fpt_rconst(y0),              \
+            (long long)fpt_rconst(s1), (long long)fpt_rconst(y1),              \
+            (long long)fpt_rconst(s2), (long long)fpt_rconst(y2));             \
+    assert(test_lookup_table_acceleration(                                     \
+               "LookupTable__SENS_MULT-" #sens_mult "-POINTS-" #s0 "_" #y0     \
+               "-" #s1 "_" #y1 "-" #s2 "_" #y2 ".snapshot",                    \
+               fpt_rconst(sens_mult), fpt_rconst(yx_ratio), points) == 0);     \
+  } while (0)
+
 int main(void) {
   test_linear(1, 1, 0, 0, 0);
   test_linear(1, 1, 0.3, 2, 2);
@@ -131,5 +301,22 @@ int main(void) {
 
   test_synchronous(1, 1.15, 0.8, 0.5, 1.5, 32);
 
+  test_lookup_table(1, 1.15, 2, 1, 8, 1.5, 32, 2.5);
+
+  test_jump(1, 1, 15, 1.5, 0);
+  test_jump(1, 1.15, 15, 1.5, 0.5);
+
+  test_power(1, 1, 1, 0.05, 0, 0);
+  test_power(0.5, 1.15, 0.1, 0.4, 1, 2.5);
+
+  test_windows(1, 1, 6, 60, 96);
+  test_windows(2, 1.15, 8, 144, 120);
+
+  test_macos(1, 1, 1);
+  test_macos(0.5, 1.15, 3);
+
+  test_classic(1, 1, 0.3, 2, 0, 0);
+  test_classic(0.5, 1.15, 0.05, 3, 2, 2.5);
+
   print_success;
 }
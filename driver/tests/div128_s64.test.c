@@ -14,14 +14,19 @@ void test_custom_division_against_fixedpt(double a, double b) {
 
   fpt quotient = div128_s64_s64(n, divisor);
   fpt quotient1 = fpt_xdiv(n, divisor);
+  // What architectures without the x86 instruction use, tested everywhere.
+  fpt quotient2 = div128_s64_s64_s64_generic(n >> FIXEDPT_FBITS,
+                                             n << FIXEDPT_FBITS, divisor);
 
   double actual = fpt_todouble(quotient);
   double expected = fpt_todouble(quotient1);
 
   dbg("actual = (%li) -> %.10f", quotient, actual);
   dbg("expect = (%li) -> %.10f", quotient1, expected);
+  dbg("generic = (%li) -> %.10f", quotient2, fpt_todouble(quotient2));
 
   assert(actual == expected);
+  assert(quotient2 == quotient1);
 #endif
 }
 
@@ -37,6 +42,14 @@ int main(void) {
 
   test_custom_division_against_fixedpt(127, 1.5);
 
+  test_custom_division_against_fixedpt(57, -5.5);
+
+  test_custom_division_against_fixedpt(-1, -3);
+
+  test_custom_division_against_fixedpt(0.001, 1000000);
+
+  test_custom_division_against_fixedpt(-21474.83647, 0.7);
+
   /* test_custom_division_against_fixedpth(135, 0); */ // You only crash once!
 
   print_success;
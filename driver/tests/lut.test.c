@@ -0,0 +1,70 @@
+#include "../accel/lut.h"
+#include "test_utils.h"
+#include <assert.h>
+#include <stdio.h>
+
+static int parses(const char *data, unsigned long len) {
+  struct lut_curve_args args = {.len = 42};
+  int result = lut_parse(data, &args);
+  return result == 0 && args.len == len;
+}
+
+static int rejects(const char *data) {
+  struct lut_curve_args args = {.len = 42};
+  return lut_parse(data, &args) == -1 && args.len == 42;
+}
+
+static void test_sens(struct lut_curve_args *args, double speed,
+                      double expected) {
+  double actual = fpt_todouble(__lut_sens_fun(fpt_rconst(speed), args));
+  dbg("lut sens at %f: %f, vs expected: %f\n", speed, actual, expected);
+  assert(actual == expected);
+}
+
+int main(void) {
+  assert(parses("", 0));
+  assert(parses("\n", 0));
+  assert(parses("0,65536", 1));
+  assert(parses("0 65536, 524288 98304\n", 2));
+  assert(parses("-65536,-65536", 1));
+
+  assert(rejects("0"));
+  assert(rejects("0,65536,0,65536"));
+  assert(rejects("524288,65536,0,65536"));
+  assert(rejects("0,1.5"));
+  assert(rejects("0,-"));
+  assert(rejects("0,6-5"));
+  assert(rejects("0,99999999999999999999999"));
+
+  char largest[64];
+  unsigned long long fpt_max = ((fptu)-1) >> 1;
+  sprintf(largest, "0,%llu", fpt_max);
+  assert(parses(largest, 1));
+  sprintf(largest, "0,%llu", fpt_max + 1);
+  assert(rejects(largest));
+
+  char too_many[2 * (LUT_MAX_POINTS + 1) * 8] = "";
+  for (int i = 0; i <= LUT_MAX_POINTS; i++) {
+    sprintf(too_many + strlen(too_many), "%d,1,", i);
+  }
+  assert(rejects(too_many));
+
+  struct lut_curve_args args = {0};
+  test_sens(&args, 10, 1);
+
+  char points[128];
+  sprintf(points, "%lld,%lld,%lld,%lld,%lld,%lld", (long long)fpt_rconst(2),
+          (long long)fpt_rconst(1), (long long)fpt_rconst(8),
+          (long long)fpt_rconst(1.5), (long long)fpt_rconst(32),
+          (long long)fpt_rconst(2.5));
+  assert(lut_parse(points, &args) == 0);
+
+  test_sens(&args, 0, 1);
+  test_sens(&args, 2, 1);
+  test_sens(&args, 5, 1.25);
+  test_sens(&args, 8, 1.5);
+  test_sens(&args, 20, 2);
+  test_sens(&args, 100, 2.5);
+
+  print_success;
+}
@@ -9,8 +9,53 @@
 
 #include "dbg.h"
 
+/*
+ * Divide the 128-bit number `high.low` by `divisor`, with a quotient that must
+ * fit in 64 bits, by shifting and subtracting one bit at a time.
+ * For architectures that can't divide a 128-bit number with one instruction,
+ * and where the kernel doesn't have a `__divti3` for the compiler to call.
+ */
+static inline uint64_t div128_u64_u64_u64(uint64_t high, uint64_t low,
+                                          uint64_t divisor) {
+  uint64_t quotient = 0;
+  uint64_t remainder = high;
+
+  for (int i = 63; i >= 0; i--) {
+    uint64_t carry = remainder >> 63;
+    remainder = (remainder << 1) | ((low >> i) & 1);
+    quotient <<= 1;
+    if (carry || remainder >= divisor) {
+      remainder -= divisor;
+      quotient |= 1;
+    }
+  }
+
+  return quotient;
+}
+
+/*
+ * Like `idivq`, rounding towards zero.
+ */
+static inline int64_t div128_s64_s64_s64_generic(int64_t high, int64_t low,
+                                                 int64_t divisor) {
+  int negative = (high < 0) != (divisor < 0);
+
+  uint64_t uhigh = high;
+  uint64_t ulow = low;
+  if (high < 0) {
+    // two's complement negation of high.low
+    ulow = -ulow;
+    uhigh = ~uhigh + (ulow == 0);
+  }
+  uint64_t udivisor = divisor < 0 ? -(uint64_t)divisor : (uint64_t)divisor;
+
+  uint64_t quotient = div128_u64_u64_u64(uhigh, ulow, udivisor);
+  return negative ? -quotient : quotient;
+}
+
 static inline int64_t div128_s64_s64_s64(int64_t high, int64_t low,
                                          int64_t divisor) {
+#if defined(__x86_64__)
   int64_t result;
   // s.high.low
   // high -> rdx
@@ -21,6 +66,9 @@ static inline int64_t div128_s64_s64_s64(int64_t high, int64_t low,
           : [B] "r"(divisor), "a"(low), "d"(high));
 
   return result;
+#else
+  return div128_s64_s64_s64_generic(high, low, divisor);
+#endif
 }
 
 static inline int is_digit(char c) { return '0' <= c && c <= '9'; }
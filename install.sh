@@ -138,7 +138,8 @@ install_driver_dkms() {
 }
 
 install_cli() {
-  if [ $(getconf LONG_BIT) -lt 64 ]; then
+  # The released CLI is only built for x86_64
+  if [ $(getconf LONG_BIT) -lt 64 ] || [ "$(uname -m)" != "x86_64" ]; then
     BUILD_CLI_FROM_SOURCE=1
   fi
 
@@ -1,16 +1,22 @@
 use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
 use maccel_core::get_param_value_from_ctx;
 use maccel_core::persist::ParamStore;
-use maccel_core::persist::SysFsStore;
 use maccel_core::Param;
 use maccel_core::ALL_COMMON_PARAMS;
+use maccel_core::ALL_JUMP_PARAMS;
 use maccel_core::ALL_LINEAR_PARAMS;
+use maccel_core::ALL_MACOS_PARAMS;
 use maccel_core::ALL_CLASSIC_PARAMS;
 use maccel_core::ALL_MODES;
 use maccel_core::ALL_NATURAL_PARAMS;
+use maccel_core::ALL_POWER_PARAMS;
 use maccel_core::ALL_SYNCHRONOUS_PARAMS;
+use maccel_core::ALL_WINDOWS_PARAMS;
 use maccel_core::{AccelMode, ContextRef, TuiContext, ALL_PARAMS};
 use ratatui::backend::Backend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Stylize;
+use ratatui::widgets::Paragraph;
 use ratatui::crossterm::event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEventKind};
 use ratatui::Terminal;
 use std::{io, time::Instant};
@@ -25,19 +31,21 @@ use crate::param_input::ParameterInput;
 use crate::screen::Screen;
 use crate::utils::CyclingIdx;
 
-pub struct App {
-    context: ContextRef<SysFsStore>,
-    screens: Vec<Screen<SysFsStore>>,
+pub struct App<PS: ParamStore> {
+    context: ContextRef<PS>,
+    screens: Vec<Screen<PS>>,
     screen_idx: CyclingIdx,
     pub(crate) is_running: bool,
+    /// The last thing that failed, shown until the next key press.
+    error: Option<String>,
 
     last_tick_at: Instant,
 }
 
-pub fn collect_inputs_for_params(
+pub fn collect_inputs_for_params<PS: ParamStore>(
     params: &[Param],
-    context: ContextRef<SysFsStore>,
-) -> Vec<ParameterInput<SysFsStore>> {
+    context: ContextRef<PS>,
+) -> Vec<ParameterInput<PS>> {
     ALL_COMMON_PARAMS
         .iter()
         .chain(params)
@@ -46,11 +54,11 @@ pub fn collect_inputs_for_params(
         .collect()
 }
 
-impl App {
-    pub fn new() -> Self {
-        let context = ContextRef::new(TuiContext::new(SysFsStore, ALL_PARAMS));
+impl<PS: ParamStore + 'static> App<PS> {
+    pub fn new(parameter_store: PS) -> maccel_core::Result<Self> {
+        let context = ContextRef::new(TuiContext::new(parameter_store, ALL_PARAMS)?);
 
-        Self {
+        Ok(Self {
             screens: vec![
                 Screen::new(
                     AccelMode::Linear,
@@ -108,6 +116,96 @@ impl App {
                         }),
                     ),
                 ),
+                Screen::new(
+                    AccelMode::Jump,
+                    collect_inputs_for_params(ALL_JUMP_PARAMS, context.clone()),
+                    Box::new(
+                        SensitivityGraph::new(context.clone()).on_y_axix_bounds_update(|ctx| {
+                            // Appropriate dynamic bounds for the Jump sens graph
+                            let upper_bound = f64::from(get_param_value_from_ctx!(ctx, SensMult))
+                                * f64::from(get_param_value_from_ctx!(ctx, JumpSens)).max(1.0)
+                                * 2.0;
+
+                            [0.0, upper_bound]
+                        }),
+                    ),
+                ),
+                Screen::new(
+                    AccelMode::Power,
+                    collect_inputs_for_params(ALL_POWER_PARAMS, context.clone()),
+                    Box::new(
+                        SensitivityGraph::new(context.clone()).on_y_axix_bounds_update(|ctx| {
+                            // Appropriate dynamic bounds for the Power sens graph
+                            let output_cap = f64::from(get_param_value_from_ctx!(ctx, OutputCapPower));
+                            let max_sens = if output_cap > 0.0 {
+                                output_cap
+                            } else {
+                                f64::from(get_param_value_from_ctx!(ctx, OutputOffsetPower)) + 1.0
+                            };
+                            let upper_bound = f64::from(get_param_value_from_ctx!(ctx, SensMult))
+                                * max_sens.max(1.0)
+                                * 2.0;
+
+                            [0.0, upper_bound]
+                        }),
+                    ),
+                ),
+                Screen::new(
+                    AccelMode::Windows,
+                    collect_inputs_for_params(ALL_WINDOWS_PARAMS, context.clone()),
+                    Box::new(
+                        SensitivityGraph::new(context.clone()).on_y_axix_bounds_update(|ctx| {
+                            // Appropriate dynamic bounds for the Windows sens graph: the gain
+                            // of Windows' curve tops out around 15, before it's scaled, by up to 2
+                            // for the slider, and to pixels per count
+                            let upper_bound = f64::from(get_param_value_from_ctx!(ctx, SensMult))
+                                * (2.0
+                                    * f64::from(get_param_value_from_ctx!(ctx, ScreenDpiWindows))
+                                    / 400.0
+                                    * f64::from(get_param_value_from_ctx!(ctx, InputDpi))
+                                    / 1000.0
+                                    * 15.0)
+                                    .max(1.0)
+                                * 2.0;
+
+                            [0.0, upper_bound]
+                        }),
+                    ),
+                ),
+                Screen::new(
+                    AccelMode::Macos,
+                    collect_inputs_for_params(ALL_MACOS_PARAMS, context.clone()),
+                    Box::new(
+                        SensitivityGraph::new(context.clone()).on_y_axix_bounds_update(|ctx| {
+                            // Appropriate dynamic bounds for the macOS sens graph
+                            let upper_bound = f64::from(get_param_value_from_ctx!(ctx, SensMult))
+                                * (1.0 + f64::from(get_param_value_from_ctx!(ctx, TrackingSpeed)))
+                                * 2.0;
+
+                            [0.0, upper_bound]
+                        }),
+                    ),
+                ),
+                Screen::new(
+                    AccelMode::LookupTable,
+                    collect_inputs_for_params(&[], context.clone()),
+                    Box::new(
+                        SensitivityGraph::new(context.clone()).on_y_axix_bounds_update(|ctx| {
+                            // Appropriate dynamic bounds for the Look-Up Table sens graph
+                            let max_sens = ctx
+                                .get()
+                                .lookup_table()
+                                .points()
+                                .iter()
+                                .fold(1.0, |max: f64, &(_, sens)| max.max(sens));
+                            let upper_bound = f64::from(get_param_value_from_ctx!(ctx, SensMult))
+                                * max_sens
+                                * 2.0;
+
+                            [0.0, upper_bound]
+                        }),
+                    ),
+                ),
             ],
             screen_idx: CyclingIdx::new_starting_at(
                 ALL_MODES.len(),
@@ -115,8 +213,9 @@ impl App {
             ),
             context,
             is_running: true,
+            error: None,
             last_tick_at: Instant::now(),
-        }
+        })
     }
 
     pub(crate) fn tick(&mut self) -> bool {
@@ -133,7 +232,7 @@ impl App {
         do_tick
     }
 
-    fn current_screen_mut(&mut self) -> &mut Screen<SysFsStore> {
+    fn current_screen_mut(&mut self) -> &mut Screen<PS> {
         let screen_idx = self.screen_idx.current();
         self.screens.get_mut(screen_idx).unwrap_or_else(|| {
             panic!(
@@ -143,7 +242,7 @@ impl App {
         })
     }
 
-    fn current_screen(&self) -> &Screen<SysFsStore> {
+    fn current_screen(&self) -> &Screen<PS> {
         let screen_idx = self.screen_idx.current();
         self.screens.get(screen_idx).unwrap_or_else(|| {
             panic!(
@@ -156,9 +255,15 @@ impl App {
     fn can_switch_screens(&self) -> bool {
         self.screens.len() > 1 && !self.current_screen().is_in_editing_mode()
     }
+
+    fn switch_mode(&mut self, accel_mode: AccelMode) -> maccel_core::Result<()> {
+        let mut context = self.context.get_mut();
+        context.update_current_mode(accel_mode)?;
+        context.reset_current_parameters()
+    }
 }
 
-impl App {
+impl<PS: ParamStore + 'static> App<PS> {
     pub(crate) fn handle_event(&mut self, event: &Event, actions: &mut Actions) {
         debug!("received event: {:?}", event);
         if let Event::Key(crossterm::event::KeyEvent {
@@ -167,26 +272,24 @@ impl App {
             ..
         }) = event
         {
+            self.error = None;
+
             match code {
                 KeyCode::Char('q') => {
                     self.is_running = false;
                     return;
                 }
-                KeyCode::Right => {
-                    if self.can_switch_screens() {
-                        self.screen_idx.forward();
-                        actions.push(Action::SetMode(
-                            self.screens[self.screen_idx.current()].accel_mode,
-                        ));
-                    }
+                KeyCode::Right if self.can_switch_screens() => {
+                    self.screen_idx.forward();
+                    actions.push(Action::SetMode(
+                        self.screens[self.screen_idx.current()].accel_mode,
+                    ));
                 }
-                KeyCode::Left => {
-                    if self.can_switch_screens() {
-                        self.screen_idx.back();
-                        actions.push(Action::SetMode(
-                            self.screens[self.screen_idx.current()].accel_mode,
-                        ));
-                    }
+                KeyCode::Left if self.can_switch_screens() => {
+                    self.screen_idx.back();
+                    actions.push(Action::SetMode(
+                        self.screens[self.screen_idx.current()].accel_mode,
+                    ));
                 }
                 _ => {}
             }
@@ -199,10 +302,15 @@ impl App {
         debug!("performing actions: {actions:?}");
 
         for action in actions.drain(..) {
-            if let Action::SetMode(accel_mode) = action {
-                self.context.get_mut().current_mode = accel_mode;
-                SysFsStore::set_current_accel_mode(accel_mode);
-                self.context.get_mut().reset_current_parameters();
+            if let Action::SetMode(accel_mode) = action
+                && let Err(err) = self.switch_mode(accel_mode)
+            {
+                self.error = Some(format!("failed to switch to {accel_mode:?} mode: {err}"));
+                // Stay on the screen of the mode the driver is still in.
+                let current_mode = self.context.get().current_mode;
+                self.screen_idx =
+                    CyclingIdx::new_starting_at(ALL_MODES.len(), current_mode.ordinal() as usize);
+                continue;
             }
 
             self.current_screen_mut().update(&action);
@@ -210,7 +318,15 @@ impl App {
     }
 
     pub(crate) fn draw(&self, frame: &mut ratatui::Frame, area: ratatui::prelude::Rect) {
-        self.current_screen().draw(frame, area);
+        let Some(error) = &self.error else {
+            self.current_screen().draw(frame, area);
+            return;
+        };
+
+        let [screen_area, error_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        self.current_screen().draw(frame, screen_area);
+        frame.render_widget(Paragraph::new(error.as_str().red()), error_area);
     }
 }
 
@@ -256,7 +372,7 @@ impl<B: Backend> Tui<B> {
     ///
     /// [`Draw`]: ratatui::Terminal::draw
     /// [`rendering`]: crate::ui::render
-    pub fn draw(&mut self, app: &mut App) -> anyhow::Result<()> {
+    pub fn draw<PS: ParamStore + 'static>(&mut self, app: &mut App<PS>) -> anyhow::Result<()> {
         self.terminal.draw(|frame| app.draw(frame, frame.area()))?;
         Ok(())
     }
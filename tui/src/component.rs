@@ -1,5 +1,3 @@
-use std::fmt::Debug;
-
 use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, Frame};
 
@@ -24,9 +22,11 @@ pub trait TuiComponent {
     fn draw(&self, frame: &mut Frame, area: Rect);
 }
 
+#[cfg(test)]
 #[derive(Debug)]
 pub struct NoopComponent;
 
+#[cfg(test)]
 impl TuiComponent for NoopComponent {
     fn handle_key_event(&mut self, _event: &KeyEvent, _actions: &mut Actions) {}
 
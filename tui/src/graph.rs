@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use maccel_core::{ContextRef, SensXY, get_param_value_from_ctx, persist::ParamStore, sensitivity};
+use maccel_core::{ContextRef, CurveConfig, SensXY, get_param_value_from_ctx, persist::ParamStore};
 
 use crate::{action, component::TuiComponent};
 
@@ -32,6 +32,10 @@ pub struct SensitivityGraph<PS: ParamStore> {
     pub y_bounds: [f64; 2],
     data: Vec<(f64, f64)>,
     data_alt: Vec<(f64, f64)>,
+    /// The points of a look-up table curve, at the sensitivity they give.
+    points: Vec<(f64, f64)>,
+    /// What's likely wrong with how the curve feels, shown under it.
+    lints: Vec<String>,
     title: &'static str,
     data_name: String,
     data_alt_name: String,
@@ -47,6 +51,8 @@ impl<PS: ParamStore> SensitivityGraph<PS> {
             y_bounds: [0.0, 0.0],
             data: vec![],
             data_alt: vec![],
+            points: vec![],
+            lints: vec![],
             title: "Sensitivity Graph (Ratio = Speed_out / Speed_in)",
             data_name: "🠠🠢 Sens".to_string(),
             data_alt_name: "🠡🠣 Sens".to_string(),
@@ -70,25 +76,38 @@ impl<PS: ParamStore> SensitivityGraph<PS> {
     fn update_data(&mut self) {
         self.data.clear();
         self.data_alt.clear();
+        self.points.clear();
 
-        let params = self.context.get().params_snapshot();
+        let curve = self.context.get().curve();
+        if let CurveConfig::LookupTable(args) = &curve.config {
+            let sens_mult = curve.common.sens_mult;
+            self.points.extend(
+                args.points
+                    .points()
+                    .iter()
+                    .map(|&(speed, sens)| (speed, sens * sens_mult)),
+            );
+        }
         for x in (0..128).map(|x| (x as f64) * 1.0 /* step size */) {
-            let (sens_x, sens_y) =
-                maccel_core::sensitivity(x, self.context.get().current_mode, &params);
+            let (sens_x, sens_y) = curve.evaluate(x);
             self.data.push((x, sens_x));
             if sens_x != sens_y {
                 self.data_alt.push((x, sens_y));
             }
         }
+
+        self.lints = maccel_core::lint::lint(curve.mode(), &curve.to_all_param_args())
+            .iter()
+            .map(|issue| format!("⚠ {issue}"))
+            .collect();
     }
 
     fn read_input_speed_and_resolved_sens(&self) -> (f64, SensXY) {
         let input_speed = maccel_core::inputspeed::read_input_speed();
-        let params = self.context.get().params_snapshot();
         debug!("last mouse move read at {} counts/ms", input_speed);
         (
             input_speed,
-            sensitivity(input_speed, self.context.get().current_mode, &params),
+            self.context.get().curve().evaluate(input_speed),
         )
     }
 
@@ -164,6 +183,17 @@ impl<PS: ParamStore> TuiComponent for SensitivityGraph<PS> {
                 .data(highlight_point_x),
         ];
 
+        if !self.points.is_empty() {
+            chart_plots.push(
+                Dataset::default()
+                    .name("• Points")
+                    .marker(symbols::Marker::Dot)
+                    .graph_type(GraphType::Scatter)
+                    .style(Style::default().cyan())
+                    .data(&self.points),
+            );
+        }
+
         if !self.data_alt.is_empty() {
             chart_plots.push(
                 Dataset::default()
@@ -188,6 +218,12 @@ impl<PS: ParamStore> TuiComponent for SensitivityGraph<PS> {
 
         let chart = Chart::new(chart_plots).x_axis(x_axis).y_axis(y_axis);
 
+        let [area, lints_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(self.lints.len() as u16),
+        ])
+        .areas(area);
+
         frame.render_widget(
             chart.block(
                 Block::default()
@@ -197,6 +233,17 @@ impl<PS: ParamStore> TuiComponent for SensitivityGraph<PS> {
             ),
             area,
         );
+
+        frame.render_widget(
+            Paragraph::new(
+                self.lints
+                    .iter()
+                    .map(|lint| Line::from(lint.as_str()))
+                    .collect::<Vec<_>>(),
+            )
+            .yellow(),
+            lints_area,
+        );
     }
 }
 
@@ -1,4 +1,6 @@
-use maccel_core::inputspeed;
+use std::path::PathBuf;
+
+use maccel_core::{inputspeed, persist::ParamStore};
 
 use event::EventHandler;
 use ratatui::{prelude::CrosstermBackend, Terminal};
@@ -13,8 +15,11 @@ mod param_input;
 mod screen;
 mod utils;
 
-pub fn run_tui() -> anyhow::Result<()> {
-    let mut app = app::App::new();
+pub fn run_tui<PS: ParamStore + 'static>(
+    parameter_store: PS,
+    input_speed_device: PathBuf,
+) -> anyhow::Result<()> {
+    let mut app = app::App::new(parameter_store)?;
 
     let backend = CrosstermBackend::new(std::io::stdout());
     let terminal = Terminal::new(backend)?;
@@ -23,7 +28,7 @@ pub fn run_tui() -> anyhow::Result<()> {
     let mut tui = app::Tui::new(terminal, events);
     tui.init()?;
 
-    let input_speed_thread_handle = inputspeed::setup_input_speed_reader();
+    let input_speed_thread_handle = inputspeed::setup_input_speed_reader(input_speed_device);
 
     let mut actions = vec![];
     while app.is_running {
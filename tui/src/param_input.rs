@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
-use anyhow::Context;
 use maccel_core::persist::ParamStore;
+use maccel_core::units::parse_param_value;
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::{prelude::*, widgets::*};
@@ -25,6 +25,7 @@ pub struct ParameterInput<PS: ParamStore> {
     input: Input,
     pub input_mode: InputMode,
     error: Option<String>,
+    warning: Option<String>,
     pub is_selected: bool,
 }
 
@@ -36,6 +37,7 @@ impl<PS: ParamStore> ParameterInput<PS> {
             input_mode: InputMode::Normal,
             input: format!("{}", param.value).into(),
             error: None,
+            warning: None,
             is_selected: false,
         }
     }
@@ -53,19 +55,20 @@ impl<PS: ParamStore> ParameterInput<PS> {
     }
 
     fn update_value(&mut self) {
-        let value = self
-            .value()
-            .parse()
-            .context("should be a number")
-            .and_then(|value| {
-                self.context
-                    .get_mut()
-                    .update_param_value(self.param_tag, value)
-            });
+        let value = parse_param_value(self.param_tag, self.value()).and_then(|value| {
+            self.context
+                .get_mut()
+                .update_param_value(self.param_tag, value)
+        });
 
         match value {
-            Ok(_) => {
+            Ok(validation) => {
                 self.error = None;
+                let warnings: Vec<_> = validation
+                    .for_param(self.param_tag)
+                    .map(|issue| issue.message.as_str())
+                    .collect();
+                self.warning = (!warnings.is_empty()).then(|| warnings.join("; "));
             }
             Err(err) => {
                 self.reset();
@@ -78,6 +81,7 @@ impl<PS: ParamStore> ParameterInput<PS> {
 
     fn reset(&mut self) {
         self.error = None;
+        self.warning = None;
         self.input = format!("{}", self.this_param().value).into();
         self.input_mode = InputMode::Normal;
     }
@@ -156,6 +160,8 @@ impl<PS: ParamStore + Debug> TuiComponent for ParameterInput<PS> {
         let input_width = area.width.max(3) - 3; // keep 2 for borders and 1 for cursor
         let input_scroll_position = self.input.visual_scroll(input_width as usize);
 
+        let info = self.param_tag.info();
+
         let mut input = Paragraph::new(self.input.value())
             .style(match self.input_mode {
                 InputMode::Normal => match self.is_selected {
@@ -168,7 +174,10 @@ impl<PS: ParamStore + Debug> TuiComponent for ParameterInput<PS> {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(self.this_param().tag.display_name()),
+                    .title(match info.unit {
+                        Some(unit) => format!("{} ({unit})", self.param_tag.display_name()),
+                        None => self.param_tag.display_name().to_string(),
+                    }),
             );
 
         match self.input_mode {
@@ -200,6 +209,20 @@ impl<PS: ParamStore + Debug> TuiComponent for ParameterInput<PS> {
             frame.render_widget(helper_text, helpher_text_layout);
 
             input = input.red();
+        } else if let Some(warning) = &self.warning {
+            let helper_text = Paragraph::new(warning.as_str())
+                .yellow()
+                .wrap(ratatui::widgets::Wrap { trim: true });
+
+            frame.render_widget(helper_text, helpher_text_layout);
+
+            input = input.yellow();
+        } else if self.is_selected {
+            let helper_text = Paragraph::new(info.help())
+                .dark_gray()
+                .wrap(ratatui::widgets::Wrap { trim: true });
+
+            frame.render_widget(helper_text, helpher_text_layout);
         }
 
         frame.render_widget(input, input_layout);
@@ -41,23 +41,27 @@ pub(crate) mod test_utils {
     pub fn new_context() -> (ContextRef<MockStore>, Vec<Parameter>) {
         let params = [
             (maccel_core::Param::SensMult, 1.0),
-            (maccel_core::Param::Accel, 1.0),
+            (maccel_core::Param::AccelLinear, 1.0),
         ];
 
         let params = params.map(|(p, v)| (p, Fpt::from(v)));
-        let context = ContextRef::new(maccel_core::TuiContext::new(
-            MockStore {
-                list: params.to_vec(),
-            },
-            &params.map(|(p, _)| p),
-        ));
+        let context = ContextRef::new(
+            maccel_core::TuiContext::new(
+                MockStore {
+                    list: params.to_vec(),
+                },
+                &params.map(|(p, _)| p),
+            )
+            .unwrap(),
+        );
 
         (context, params.map(|(p, v)| Parameter::new(p, v)).to_vec())
     }
 
     mod mocks {
-        use anyhow::Context;
-        use maccel_core::{fixedptc::Fpt, persist::ParamStore, AccelMode, Param};
+        use maccel_core::{
+            AccelMode, Error, LookupTable, Param, fixedptc::Fpt, persist::ParamStore,
+        };
 
         #[derive(Debug)]
         pub struct MockStore {
@@ -65,27 +69,41 @@ pub(crate) mod test_utils {
         }
 
         impl ParamStore for MockStore {
-            fn set(&mut self, param: Param, value: f64) -> anyhow::Result<()> {
+            fn set(&mut self, param: Param, value: f64) -> maccel_core::Result<()> {
                 if !self.list.iter().any(|(p, _)| p == &param) {
                     self.list.push((param, value.into()));
                 }
                 Ok(())
             }
 
-            fn get(&self, param: &Param) -> anyhow::Result<Fpt> {
+            fn get(&self, param: &Param) -> maccel_core::Result<Fpt> {
                 self.list
                     .iter()
                     .find(|(p, _)| p == param)
                     .map(|(_, v)| v)
                     .copied()
-                    .context("failed to get param")
+                    .ok_or(Error::UnsupportedParameter { name: param.name() })
             }
 
-            fn set_current_accel_mode(_mode: maccel_core::AccelMode) {
+            fn set_unchecked(&mut self, param: Param, value: Fpt) -> maccel_core::Result<()> {
+                self.set(param, value.into())
+            }
+
+            fn set_current_accel_mode(
+                &mut self,
+                _mode: maccel_core::AccelMode,
+            ) -> maccel_core::Result<()> {
+                unimplemented!()
+            }
+            fn get_current_accel_mode(&self) -> maccel_core::Result<maccel_core::AccelMode> {
+                Ok(AccelMode::Linear)
+            }
+
+            fn set_lookup_table(&mut self, _table: &LookupTable) -> maccel_core::Result<()> {
                 unimplemented!()
             }
-            fn get_current_accel_mode() -> maccel_core::AccelMode {
-                AccelMode::Linear
+            fn get_lookup_table(&self) -> maccel_core::Result<LookupTable> {
+                Ok(LookupTable::default())
             }
         }
     }